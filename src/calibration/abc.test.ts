@@ -0,0 +1,70 @@
+// src/calibration/abc.test.ts
+import { describe, it, expect } from 'vitest'
+import { abcRejection, abcSMC, BatchSimulator, Bounds, Particle } from './abc'
+import { createRng } from '../rng'
+
+// toy model: y ~ Normal(θ₀, 0.2), θ₁ has no effect; observed y = 1
+const bounds: Bounds = [{ min: -5, max: 5 }, { min: 0, max: 1 }]
+const noise = createRng(99)
+const simulate: BatchSimulator = async thetas => thetas.map(t => ({ y: noise.normal(t[0], 0.2) }))
+const distance = (stats: Record<string, number>) => Math.abs(stats.y - 1)
+
+const weightedMean = (ps: Particle[], d: number) => ps.reduce((s, p) => s + p.weight * p.theta[d], 0)
+const weightedSd = (ps: Particle[], d: number) => {
+  const m = weightedMean(ps, d)
+  return Math.sqrt(ps.reduce((s, p) => s + p.weight * (p.theta[d] - m) ** 2, 0))
+}
+
+describe('Approximate Bayesian Computation - abc.ts', () => {
+  describe('abcRejection', () => {
+    it('should keep the closest draws with equal weights', async () => {
+      const particles = await abcRejection(bounds, simulate, distance, { particles: 100, draws: 2000 }, createRng(1))
+
+      expect(particles).toHaveLength(100)
+      particles.forEach(p => expect(p.weight).toBeCloseTo(0.01))
+      const worst = Math.max(...particles.map(p => p.distance))
+      expect(particles.every(p => p.distance <= worst)).toBe(true)
+      expect(Math.abs(weightedMean(particles, 0) - 1)).toBeLessThan(0.15)
+    })
+
+    it('should never accept runs with an infinite distance', async () => {
+      const broken: BatchSimulator = async thetas => thetas.map(t => ({ y: t[0] > 0 ? NaN : t[0] }))
+      const particles = await abcRejection(bounds, broken, s => Number.isNaN(s.y) ? Infinity : Math.abs(s.y), { particles: 10, draws: 100 }, createRng(2))
+      particles.forEach(p => expect(p.theta[0]).toBeLessThanOrEqual(0))
+    })
+  })
+
+  describe('abcSMC', () => {
+    it('should concentrate on the posterior and tighten the tolerance', async () => {
+      const tolerances: number[] = []
+      const particles = await abcSMC(bounds, simulate, distance, {
+        particles: 200,
+        populations: 5,
+        onPopulation: (_, epsilon) => tolerances.push(epsilon),
+      }, createRng(3))
+
+      expect(particles).toHaveLength(200)
+      expect(particles.reduce((s, p) => s + p.weight, 0)).toBeCloseTo(1)
+      expect(tolerances).toHaveLength(5)
+      tolerances.slice(1).forEach((e, i) => expect(e).toBeLessThan(tolerances[i]))
+
+      // posterior of θ₀ is about Normal(1, 0.2); θ₁ stays spread over its prior
+      expect(Math.abs(weightedMean(particles, 0) - 1)).toBeLessThan(0.1)
+      expect(weightedSd(particles, 0)).toBeLessThan(0.4)
+      expect(weightedSd(particles, 1)).toBeGreaterThan(0.2)
+      particles.forEach(p => {
+        expect(p.theta[1]).toBeGreaterThanOrEqual(0)
+        expect(p.theta[1]).toBeLessThanOrEqual(1)
+      })
+    })
+
+    it('should return the last complete population when one cannot be filled', async () => {
+      // every proposal after the first population is too far
+      let calls = 0
+      const stuck: BatchSimulator = async thetas => thetas.map(() => ({ y: calls++ < 20 ? 1 + calls / 100 : 100 }))
+      const particles = await abcSMC(bounds, stuck, distance, { particles: 10, maxBatches: 3 }, createRng(4))
+      expect(particles).toHaveLength(10)
+      particles.forEach(p => expect(p.distance).toBeLessThan(1))
+    })
+  })
+})
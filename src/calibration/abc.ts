@@ -0,0 +1,140 @@
+// src/calibration/abc.ts
+// Approximate Bayesian computation: rejection and sequential Monte Carlo (ABC-SMC)
+// under uniform priors, with the model behind a batch simulator.
+import { Rng } from '../rng'
+import { quantile } from '../metrics/inequality'
+
+/** Uniform prior bounds, one per parameter */
+export type Bounds = { min: number, max: number }[]
+
+/** Simulate a batch of parameter vectors (possibly in parallel) and return their statistics */
+export type BatchSimulator = (thetas: number[][]) => Promise<Record<string, number>[]>
+
+export interface Particle {
+  theta: number[]
+  /** Normalized importance weight (equal after rejection sampling) */
+  weight: number
+  distance: number
+  stats: Record<string, number>
+}
+
+export interface AbcOptions {
+  /** Particles in the posterior sample */
+  particles: number
+  /** Rejection: prior draws, of which the `particles` closest are kept (default 10 × particles) */
+  draws?: number
+  /** SMC: number of populations, each with a tighter tolerance (default 5) */
+  populations?: number
+  /** SMC: each tolerance is this quantile of the previous population's distances (default 0.5) */
+  quantile?: number
+  /** SMC: proposals simulated per batch (default particles) */
+  batchSize?: number
+  /** SMC: give up on a population after this many batches (default 50) */
+  maxBatches?: number
+  /** Progress after each population: its tolerance and acceptance rate */
+  onPopulation?: (population: number, epsilon: number, acceptance: number) => void
+}
+
+const samplePrior = (bounds: Bounds, rng: Rng) => bounds.map(({ min, max }) => min + rng.random() * (max - min))
+
+const inSupport = (theta: number[], bounds: Bounds) => theta.every((x, d) => x >= bounds[d].min && x <= bounds[d].max)
+
+/** Simulate thetas and pair them with their distances */
+async function evaluate(
+  thetas: number[][],
+  simulate: BatchSimulator,
+  distance: (stats: Record<string, number>) => number
+): Promise<Omit<Particle, 'weight'>[]> {
+  const stats = await simulate(thetas)
+  return thetas.map((theta, i) => ({ theta, stats: stats[i], distance: distance(stats[i]) }))
+}
+
+/**
+ * ABC rejection: simulate `draws` prior samples and keep the `particles`
+ * closest to the targets, i.e. a tolerance at that quantile of the distances.
+ */
+export async function abcRejection(
+  bounds: Bounds,
+  simulate: BatchSimulator,
+  distance: (stats: Record<string, number>) => number,
+  options: AbcOptions,
+  rng: Rng
+): Promise<Particle[]> {
+  const draws = options.draws ?? 10 * options.particles
+  const thetas = Array.from({ length: draws }, () => samplePrior(bounds, rng))
+  const evaluated = await evaluate(thetas, simulate, distance)
+  const kept = evaluated
+    .filter(p => Number.isFinite(p.distance))
+    .sort((a, b) => a.distance - b.distance)
+    .slice(0, options.particles)
+  options.onPopulation?.(0, kept[kept.length - 1]?.distance ?? Infinity, kept.length / draws)
+  return kept.map(p => ({ ...p, weight: 1 / kept.length }))
+}
+
+/** Weighted per-dimension variance of a population */
+function weightedVariance(population: Particle[], d: number): number {
+  const m = population.reduce((s, p) => s + p.weight * p.theta[d], 0)
+  return population.reduce((s, p) => s + p.weight * (p.theta[d] - m) ** 2, 0)
+}
+
+/**
+ * ABC-SMC (population Monte Carlo, Beaumont et al. 2009). The first population
+ * is the closest half of `particles / quantile` prior draws; each later one is
+ * filled by perturbing particles of the previous population with a Gaussian
+ * kernel of twice its weighted variance, accepting proposals within the
+ * tolerance and reweighting by prior / proposal density. Stops early, returning
+ * the last complete population, if a population can't be filled in `maxBatches`.
+ */
+export async function abcSMC(
+  bounds: Bounds,
+  simulate: BatchSimulator,
+  distance: (stats: Record<string, number>) => number,
+  options: AbcOptions,
+  rng: Rng
+): Promise<Particle[]> {
+  const N = options.particles
+  const alpha = options.quantile ?? 0.5
+  const batchSize = options.batchSize ?? N
+  const maxBatches = options.maxBatches ?? 50
+
+  let population = await abcRejection(
+    bounds, simulate, distance,
+    { particles: N, draws: Math.ceil(N / alpha), onPopulation: options.onPopulation },
+    rng
+  )
+
+  for (let t = 1; t < (options.populations ?? 5); t++) {
+    const epsilon = quantile(population.map(p => p.distance), alpha)
+    const sd = bounds.map((_, d) => Math.sqrt(2 * weightedVariance(population, d)))
+    const cumulative: number[] = []
+    population.reduce((s, p, i) => (cumulative[i] = s + p.weight), 0)
+
+    const propose = (): number[] => {
+      for (;;) {
+        const u = rng.random() * cumulative[cumulative.length - 1]
+        const j = cumulative.findIndex(c => c > u)
+        const parent = population[j < 0 ? population.length - 1 : j]
+        const theta = parent.theta.map((x, d) => rng.normal(x, sd[d]))
+        if (inSupport(theta, bounds)) return theta
+      }
+    }
+
+    const accepted: Omit<Particle, 'weight'>[] = []
+    let simulated = 0
+    for (let batch = 0; batch < maxBatches && accepted.length < N; batch++) {
+      const evaluated = await evaluate(Array.from({ length: batchSize }, propose), simulate, distance)
+      simulated += batchSize
+      evaluated.forEach(p => { if (p.distance <= epsilon && accepted.length < N) accepted.push(p) })
+    }
+    if (accepted.length < N) break
+
+    // importance weights: uniform prior over the proposal mixture density
+    const raw = accepted.map(p => 1 / population.reduce((s, q) =>
+      s + q.weight * Math.exp(-0.5 * p.theta.reduce((e, x, d) =>
+        e + (sd[d] > 0 ? ((x - q.theta[d]) / sd[d]) ** 2 : 0), 0)), 0))
+    const total = raw.reduce((s, w) => s + w, 0)
+    population = accepted.map((p, i) => ({ ...p, weight: raw[i] / total }))
+    options.onPopulation?.(t, epsilon, N / simulated)
+  }
+  return population
+}
@@ -0,0 +1,46 @@
+// src/calibration/calibrate.test.ts
+import { describe, it, expect } from 'vitest'
+import { runCalibration, particlesToCSV } from './calibrate'
+import { validateCalibrationSpec } from './targets'
+
+const spec = validateCalibrationSpec({
+  targets: { gini: { value: 0.5 } },
+  priors: { geneEnvWeight: { min: 0, max: 1 }, 'homophily.wealth': { min: -1, max: 1 } },
+  base: { populationSize: 50 },
+  generations: 2,
+  window: 2,
+})
+
+describe('Calibration - calibrate.ts', () => {
+  describe('runCalibration', () => {
+    it('should return posterior samples inside the priors', async () => {
+      const particles = await runCalibration(spec, { method: 'rejection', particles: 5, draws: 10 }, { workers: 1 })
+
+      expect(particles).toHaveLength(5)
+      particles.forEach(p => {
+        expect(p.theta[0]).toBeGreaterThanOrEqual(0)
+        expect(p.theta[0]).toBeLessThanOrEqual(1)
+        expect(p.theta[1]).toBeGreaterThanOrEqual(-1)
+        expect(Number.isFinite(p.stats.gini)).toBe(true)
+      })
+    })
+
+    it('should be reproducible from the seed', async () => {
+      const options = { method: 'smc' as const, particles: 4, populations: 2, seed: 3 }
+      const a = await runCalibration(spec, options, { workers: 1 })
+      const b = await runCalibration(spec, options, { workers: 1 })
+      expect(a).toEqual(b)
+    })
+  })
+
+  describe('particlesToCSV', () => {
+    it('should write weights, distances, parameters and statistics', () => {
+      const csv = particlesToCSV(
+        [{ theta: [0.5, -0.25], weight: 1, distance: 0.1, stats: { gini: 0.55 } }],
+        ['geneEnvWeight', 'homophily.wealth'],
+        ['gini']
+      )
+      expect(csv).toBe('particle,weight,distance,geneEnvWeight,homophily.wealth,sim_gini\n0,1,0.1,0.5,-0.25,0.55\n')
+    })
+  })
+})
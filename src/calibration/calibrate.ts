@@ -0,0 +1,65 @@
+// src/calibration/calibrate.ts
+// Fitting Params to a target file with ABC, running the model on the sweep pool.
+import { Params } from '../model'
+import { createRng } from '../rng'
+import { mergeParams } from '../params'
+import { csvRow } from '../csv'
+import { pointOverrides, SweepPoint } from '../sweep/design'
+import { SweepJob } from '../sweep/jobs'
+import { PoolOptions, runJobs } from '../sweep/pool'
+import { steadyState } from '../sensitivity/analysis'
+import { CalibrationSpec, targetDistance } from './targets'
+import { AbcOptions, Bounds, Particle, abcRejection, abcSMC } from './abc'
+
+export interface CalibrationOptions extends AbcOptions {
+  method: 'rejection' | 'smc'
+  seed?: number
+}
+
+/**
+ * Posterior sample of the prior parameters given the targets. Each simulation
+ * gets its own seed from the calibration's generator.
+ */
+export async function runCalibration(
+  spec: CalibrationSpec,
+  options: CalibrationOptions,
+  pool: Omit<PoolOptions, 'onResult'>
+): Promise<Particle[]> {
+  const priors = spec.priors ?? {}
+  const names = Object.keys(priors)
+  const bounds: Bounds = names.map(n => priors[n])
+  const metrics = Object.keys(spec.targets)
+  const generations = spec.generations ?? 50
+  const window = spec.window ?? 10
+  const rng = createRng(options.seed ?? 1)
+  const base: Params = mergeParams(spec.base ?? {})
+
+  let nextJob = 0
+  const simulate = async (thetas: number[][]) => {
+    const jobs: SweepJob[] = thetas.map(theta => {
+      const point: SweepPoint = {}
+      names.forEach((n, d) => { point[n] = theta[d] })
+      const seed = rng.int(2 ** 31)
+      return {
+        point: nextJob++,
+        replicate: 0,
+        seed,
+        generations,
+        params: { ...mergeParams(pointOverrides(point), base), seed },
+      }
+    })
+    const results = await runJobs(jobs, pool)
+    return results.map(r => Object.fromEntries(metrics.map(m => [m, steadyState(r.history, m, window)])))
+  }
+  const distance = (stats: Record<string, number>) => targetDistance(stats, spec.targets)
+
+  return options.method === 'rejection'
+    ? abcRejection(bounds, simulate, distance, options, rng)
+    : abcSMC(bounds, simulate, distance, options, rng)
+}
+
+/** Posterior samples as CSV: weight, distance, parameters and the simulated statistics */
+export function particlesToCSV(particles: Particle[], names: string[], metrics: string[]): string {
+  return csvRow(['particle', 'weight', 'distance', ...names, ...metrics.map(m => `sim_${m}`)])
+    + particles.map((p, i) => csvRow([i, p.weight, p.distance, ...p.theta, ...metrics.map(m => p.stats[m])])).join('')
+}
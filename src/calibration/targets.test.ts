@@ -0,0 +1,63 @@
+// src/calibration/targets.test.ts
+import { describe, it, expect } from 'vitest'
+import { readFileSync } from 'node:fs'
+import { targetScale, targetDistance, validateCalibrationSpec } from './targets'
+import { DEFAULT_FACTORS } from '../sensitivity/analysis'
+
+const readTargets = (file: string) =>
+  JSON.parse(readFileSync(new URL(`../../calibration/${file}`, import.meta.url), 'utf8'))
+
+describe('Calibration Targets - targets.ts', () => {
+  describe('targetScale', () => {
+    it('should default to 10% of a value, or half a band', () => {
+      expect(targetScale({ value: 0.7 })).toBeCloseTo(0.07)
+      expect(targetScale({ value: 0.02 })).toBe(0.01)
+      expect(targetScale({ min: 0.4, max: 0.5 })).toBeCloseTo(0.05)
+      expect(targetScale({ value: 0.7, scale: 0.2 })).toBe(0.2)
+    })
+  })
+
+  describe('targetDistance', () => {
+    it('should be zero on target and inside bands', () => {
+      expect(targetDistance({ gini: 0.85, ige: 0.45 }, { gini: { value: 0.85 }, ige: { min: 0.4, max: 0.5 } })).toBe(0)
+    })
+
+    it('should be the root mean square of scaled deviations', () => {
+      // (0.1 / 0.1)² and (0.05 / 0.05)² average to 1
+      const d = targetDistance(
+        { gini: 0.9, ige: 0.55 },
+        { gini: { value: 1, scale: 0.1 }, ige: { min: 0.4, max: 0.5 } }
+      )
+      expect(d).toBeCloseTo(1)
+    })
+
+    it('should be infinite when a statistic is missing', () => {
+      expect(targetDistance({ gini: NaN }, { gini: { value: 0.85 } })).toBe(Infinity)
+      expect(targetDistance({}, { ige: { value: 0.4 } })).toBe(Infinity)
+    })
+  })
+
+  describe('validateCalibrationSpec', () => {
+    it('should fill in the default priors', () => {
+      const spec = validateCalibrationSpec({ targets: { gini: { value: 0.85 } } })
+      expect(spec.priors).toEqual(DEFAULT_FACTORS)
+    })
+
+    it('should reject malformed targets and priors', () => {
+      expect(() => validateCalibrationSpec({ targets: {} })).toThrow('targets')
+      expect(() => validateCalibrationSpec({ targets: { gini: 0.85 } })).toThrow('gini')
+      expect(() => validateCalibrationSpec({ targets: { ige: { min: 0.5, max: 0.4 } } })).toThrow('ige')
+      expect(() => validateCalibrationSpec({ targets: { gini: { value: 0.85, scale: 0 } } })).toThrow('scale')
+      expect(() => validateCalibrationSpec({
+        targets: { gini: { value: 0.85 } }, priors: { financeWeight: { min: 1, max: 0 } },
+      })).toThrow('financeWeight')
+    })
+
+    it('should accept the shipped target files', () => {
+      expect(Object.keys(validateCalibrationSpec(readTargets('targets-us.json')).targets))
+        .toEqual(['gini', 'top10', 'bottom50', 'ige'])
+      expect(Object.keys(validateCalibrationSpec(readTargets('targets-nordic.json')).targets))
+        .toEqual(['ige'])
+    })
+  })
+})
@@ -0,0 +1,71 @@
+// src/calibration/targets.ts
+// Empirical targets and the distance of a simulated run from them.
+import { DEFAULT_FACTORS, FactorRanges } from '../sensitivity/analysis'
+
+/**
+ * One target statistic: a point value, or an acceptable [min, max] band (zero
+ * distance inside). `scale` sets how far off counts as one unit of distance;
+ * by default 10% of the value (at least 0.01) or half the band's width.
+ */
+export type Target =
+  | { value: number, scale?: number }
+  | { min: number, max: number, scale?: number }
+
+/** A calibration problem, usually read from a JSON target file */
+export interface CalibrationSpec {
+  /** Targets keyed by history metric (e.g. gini, top10, bottom50, ige) */
+  targets: Record<string, Target>
+  /** Uniform priors over parameter ranges; DEFAULT_FACTORS if absent */
+  priors?: FactorRanges
+  /** Overrides applied to the defaults in every run */
+  base?: Record<string, unknown>
+  generations?: number
+  /** Statistics are means over the last `window` generations */
+  window?: number
+}
+
+const hasBand = (t: Target): t is { min: number, max: number, scale?: number } => 'min' in t
+
+export function targetScale(t: Target): number {
+  if (t.scale !== undefined) return t.scale
+  return hasBand(t) ? Math.max((t.max - t.min) / 2, 1e-9) : Math.max(0.1 * Math.abs(t.value), 0.01)
+}
+
+/**
+ * Root mean square of the scaled deviations from every target. A missing or
+ * non-finite statistic gives an infinite distance, so such runs are never accepted.
+ */
+export function targetDistance(stats: Record<string, number>, targets: Record<string, Target>): number {
+  const deviations = Object.entries(targets).map(([metric, t]) => {
+    const x = stats[metric]
+    if (x === undefined || !Number.isFinite(x)) return Infinity
+    const off = hasBand(t) ? Math.max(0, t.min - x, x - t.max) : x - t.value
+    return off / targetScale(t)
+  })
+  if (deviations.length === 0) return 0
+  return Math.sqrt(deviations.reduce((s, d) => s + d * d, 0) / deviations.length)
+}
+
+/** Check the shape of a parsed target file and fill in the default priors */
+export function validateCalibrationSpec(raw: unknown): CalibrationSpec {
+  const spec = raw as CalibrationSpec
+  if (typeof spec !== 'object' || spec === null) throw new Error('Target file must be an object')
+  if (typeof spec.targets !== 'object' || spec.targets === null || Object.keys(spec.targets).length === 0) {
+    throw new Error('Target file needs at least one entry in "targets"')
+  }
+  Object.entries(spec.targets).forEach(([metric, t]) => {
+    const ok = typeof t === 'object' && t !== null && (
+      'min' in t ? typeof t.min === 'number' && typeof t.max === 'number' && t.min <= t.max
+        : typeof (t as { value: unknown }).value === 'number'
+    )
+    if (!ok) throw new Error(`Target ${metric} needs a numeric "value" or a "min" ≤ "max" band`)
+    if (t.scale !== undefined && !(t.scale > 0)) throw new Error(`Target ${metric} needs a positive "scale"`)
+  })
+  const priors = spec.priors ?? DEFAULT_FACTORS
+  Object.entries(priors).forEach(([name, r]) => {
+    if (!(typeof r?.min === 'number' && typeof r?.max === 'number' && r.min < r.max)) {
+      throw new Error(`Prior ${name} needs a "min" < "max" range`)
+    }
+  })
+  return { ...spec, priors }
+}
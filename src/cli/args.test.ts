@@ -0,0 +1,90 @@
+// src/cli/args.test.ts
+import { describe, it, expect } from 'vitest'
+import { parseRunArgs, parseSweepArgs, parseSensitivityArgs, parseCalibrateArgs } from './args'
+
+describe('CLI Arguments - args.ts', () => {
+  describe('parseRunArgs', () => {
+    it('should apply defaults', () => {
+      const args = parseRunArgs([])
+      expect(args.generations).toBe(100)
+      expect(args.snapshotEvery).toBe(0)
+      expect(args.seed).toBeUndefined()
+      expect(args.out).toBeUndefined()
+      expect(args.untilConverged).toBe(false)
+      expect(args.convergeOn).toEqual(['gini'])
+      expect(args.help).toBe(false)
+    })
+
+    it('should parse long and short options', () => {
+      const args = parseRunArgs(['--params', 'p.json', '-g', '20', '--seed', '1', '-o', 'run.csv', '--agents', 'a.csv', '--snapshot-every', '5'])
+      expect(args).toEqual({
+        params: 'p.json', generations: 20, seed: 1, out: 'run.csv',
+        agents: 'a.csv', snapshotEvery: 5, untilConverged: false, convergeOn: ['gini'], help: false,
+      })
+    })
+
+    it('should parse the convergence options', () => {
+      const args = parseRunArgs(['--until-converged', '--converge-on', 'gini, rankRankSlope'])
+      expect(args.untilConverged).toBe(true)
+      expect(args.convergeOn).toEqual(['gini', 'rankRankSlope'])
+    })
+
+    it('should reject malformed numbers', () => {
+      expect(() => parseRunArgs(['--generations', 'ten'])).toThrow('--generations')
+      expect(() => parseRunArgs(['--seed', '-1'])).toThrow('--seed')
+    })
+
+    it('should reject unknown options', () => {
+      expect(() => parseRunArgs(['--generation', '10'])).toThrow()
+    })
+  })
+
+  describe('parseSweepArgs', () => {
+    it('should leave spec overrides unset by default', () => {
+      const args = parseSweepArgs(['--spec', 'sweep.json'])
+      expect(args.spec).toBe('sweep.json')
+      expect(args.workers).toBeUndefined()
+      expect(args.replicates).toBeUndefined()
+      expect(args.generations).toBeUndefined()
+    })
+
+    it('should parse overrides', () => {
+      const args = parseSweepArgs(['--spec', 's.json', '-w', '4', '-r', '10', '-g', '30', '--summary', 'sum.csv'])
+      expect(args).toMatchObject({ workers: 4, replicates: 10, generations: 30, summary: 'sum.csv' })
+    })
+
+    it('should need at least one worker and replicate', () => {
+      expect(() => parseSweepArgs(['--workers', '0'])).toThrow('--workers')
+      expect(() => parseSweepArgs(['--replicates', '0'])).toThrow('--replicates')
+    })
+  })
+
+  describe('parseSensitivityArgs', () => {
+    it('should parse the method and sample sizes', () => {
+      const args = parseSensitivityArgs(['-m', 'morris', '-r', '30', '-n', '128'])
+      expect(args).toMatchObject({ method: 'morris', trajectories: 30, samples: 128 })
+      expect(args.spec).toBeUndefined()
+    })
+
+    it('should reject unknown methods', () => {
+      expect(() => parseSensitivityArgs(['--method', 'fast'])).toThrow('--method')
+    })
+  })
+
+  describe('parseCalibrateArgs', () => {
+    it('should default to ABC-SMC with 100 particles', () => {
+      const args = parseCalibrateArgs(['--targets', 'us.json'])
+      expect(args).toMatchObject({ targets: 'us.json', method: 'smc', particles: 100, populations: 5 })
+      expect(args.draws).toBeUndefined()
+    })
+
+    it('should parse rejection options', () => {
+      expect(parseCalibrateArgs(['-m', 'rejection', '-n', '50', '--draws', '5000']))
+        .toMatchObject({ method: 'rejection', particles: 50, draws: 5000 })
+    })
+
+    it('should reject unknown methods', () => {
+      expect(() => parseCalibrateArgs(['--method', 'mcmc'])).toThrow('--method')
+    })
+  })
+})
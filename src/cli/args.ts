@@ -0,0 +1,205 @@
+// src/cli/args.ts
+// Command-line options of `wealth-abm run`.
+import { parseArgs } from 'node:util'
+
+export interface RunArgs {
+  /** JSON file of parameter overrides */
+  params?: string
+  generations: number
+  /** Overrides params.seed */
+  seed?: number
+  /** Per-generation metrics CSV; stdout if absent */
+  out?: string
+  /** Agent snapshot CSV; no snapshots if absent */
+  agents?: string
+  /** Snapshot every k-th generation (0 = final generation only) */
+  snapshotEvery: number
+  /** Stop once every convergence metric has reached its steady state */
+  untilConverged: boolean
+  /** Metrics tested for convergence */
+  convergeOn: string[]
+  help: boolean
+}
+
+function integer(name: string, value: string, min: number): number {
+  const n = Number(value)
+  if (!Number.isInteger(n) || n < min) throw new Error(`--${name} must be an integer ≥ ${min}, got '${value}'`)
+  return n
+}
+
+/** Parse the arguments following `run`; throws on unknown or malformed options */
+export function parseRunArgs(argv: string[]): RunArgs {
+  const { values } = parseArgs({
+    args: argv,
+    options: {
+      params:            { type: 'string', short: 'p' },
+      generations:       { type: 'string', short: 'g', default: '100' },
+      seed:              { type: 'string', short: 's' },
+      out:               { type: 'string', short: 'o' },
+      agents:            { type: 'string', short: 'a' },
+      'snapshot-every':  { type: 'string', default: '0' },
+      'until-converged': { type: 'boolean', default: false },
+      'converge-on':     { type: 'string', default: 'gini' },
+      help:              { type: 'boolean', short: 'h', default: false },
+    },
+  })
+  return {
+    params:         values.params,
+    generations:    integer('generations', values.generations as string, 0),
+    seed:           values.seed === undefined ? undefined : integer('seed', values.seed, 0),
+    out:            values.out,
+    agents:         values.agents,
+    snapshotEvery:  integer('snapshot-every', values['snapshot-every'] as string, 0),
+    untilConverged: values['until-converged'] as boolean,
+    convergeOn:     (values['converge-on'] as string).split(',').map(m => m.trim()).filter(m => m !== ''),
+    help:           values.help as boolean,
+  }
+}
+
+export interface SweepArgs {
+  /** JSON sweep specification (see SweepSpec) */
+  spec?: string
+  /** Tidy long-format CSV; stdout if absent */
+  out?: string
+  /** Per point × generation × metric replicate means and sds */
+  summary?: string
+  /** Worker threads; defaults to the available parallelism */
+  workers?: number
+  /** Override the spec's replicates, generations and seed */
+  replicates?: number
+  generations?: number
+  seed?: number
+  help: boolean
+}
+
+/** Parse the arguments following `sweep` */
+export function parseSweepArgs(argv: string[]): SweepArgs {
+  const { values } = parseArgs({
+    args: argv,
+    options: {
+      spec:        { type: 'string' },
+      out:         { type: 'string', short: 'o' },
+      summary:     { type: 'string' },
+      workers:     { type: 'string', short: 'w' },
+      replicates:  { type: 'string', short: 'r' },
+      generations: { type: 'string', short: 'g' },
+      seed:        { type: 'string', short: 's' },
+      help:        { type: 'boolean', short: 'h', default: false },
+    },
+  })
+  const optional = (name: string, value: string | undefined, min: number) =>
+    value === undefined ? undefined : integer(name, value, min)
+  return {
+    spec:        values.spec,
+    out:         values.out,
+    summary:     values.summary,
+    workers:     optional('workers', values.workers, 1),
+    replicates:  optional('replicates', values.replicates, 1),
+    generations: optional('generations', values.generations, 0),
+    seed:        optional('seed', values.seed, 0),
+    help:        values.help as boolean,
+  }
+}
+
+export interface SensitivityArgs {
+  /** JSON sensitivity specification (see SensitivitySpec); defaults if absent */
+  spec?: string
+  /** Index table CSV; stdout if absent */
+  out?: string
+  workers?: number
+  /** Override the spec's method, sample sizes and seed */
+  method?: 'sobol' | 'morris'
+  samples?: number
+  trajectories?: number
+  generations?: number
+  seed?: number
+  help: boolean
+}
+
+/** Parse the arguments following `sensitivity` */
+export function parseSensitivityArgs(argv: string[]): SensitivityArgs {
+  const { values } = parseArgs({
+    args: argv,
+    options: {
+      spec:         { type: 'string' },
+      out:          { type: 'string', short: 'o' },
+      workers:      { type: 'string', short: 'w' },
+      method:       { type: 'string', short: 'm' },
+      samples:      { type: 'string', short: 'n' },
+      trajectories: { type: 'string', short: 'r' },
+      generations:  { type: 'string', short: 'g' },
+      seed:         { type: 'string', short: 's' },
+      help:         { type: 'boolean', short: 'h', default: false },
+    },
+  })
+  const optional = (name: string, value: string | undefined, min: number) =>
+    value === undefined ? undefined : integer(name, value, min)
+  if (values.method !== undefined && values.method !== 'sobol' && values.method !== 'morris') {
+    throw new Error(`--method must be sobol or morris, got '${values.method}'`)
+  }
+  return {
+    spec:         values.spec,
+    out:          values.out,
+    workers:      optional('workers', values.workers, 1),
+    method:       values.method as SensitivityArgs['method'],
+    samples:      optional('samples', values.samples, 2),
+    trajectories: optional('trajectories', values.trajectories, 2),
+    generations:  optional('generations', values.generations, 1),
+    seed:         optional('seed', values.seed, 0),
+    help:         values.help as boolean,
+  }
+}
+
+export interface CalibrateArgs {
+  /** JSON target file (see CalibrationSpec) */
+  targets?: string
+  /** Posterior samples CSV; stdout if absent */
+  out?: string
+  workers?: number
+  method: 'rejection' | 'smc'
+  particles: number
+  /** Rejection: prior draws (default 10 × particles) */
+  draws?: number
+  /** SMC: populations */
+  populations: number
+  /** Override the target file's generations */
+  generations?: number
+  seed?: number
+  help: boolean
+}
+
+/** Parse the arguments following `calibrate` */
+export function parseCalibrateArgs(argv: string[]): CalibrateArgs {
+  const { values } = parseArgs({
+    args: argv,
+    options: {
+      targets:     { type: 'string', short: 't' },
+      out:         { type: 'string', short: 'o' },
+      workers:     { type: 'string', short: 'w' },
+      method:      { type: 'string', short: 'm', default: 'smc' },
+      particles:   { type: 'string', short: 'n', default: '100' },
+      draws:       { type: 'string' },
+      populations: { type: 'string', default: '5' },
+      generations: { type: 'string', short: 'g' },
+      seed:        { type: 'string', short: 's' },
+      help:        { type: 'boolean', short: 'h', default: false },
+    },
+  })
+  const optional = (name: string, value: string | undefined, min: number) =>
+    value === undefined ? undefined : integer(name, value, min)
+  if (values.method !== 'rejection' && values.method !== 'smc') {
+    throw new Error(`--method must be rejection or smc, got '${values.method}'`)
+  }
+  return {
+    targets:     values.targets,
+    out:         values.out,
+    workers:     optional('workers', values.workers, 1),
+    method:      values.method as CalibrateArgs['method'],
+    particles:   integer('particles', values.particles as string, 2),
+    draws:       optional('draws', values.draws, 1),
+    populations: integer('populations', values.populations as string, 1),
+    generations: optional('generations', values.generations, 1),
+    seed:        optional('seed', values.seed, 0),
+    help:        values.help as boolean,
+  }
+}
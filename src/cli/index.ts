@@ -0,0 +1,260 @@
+// src/cli/index.ts
+// Entry point of the `wealth-abm` command (see vite.cli.config.ts for the build).
+import { appendFileSync, readFileSync, writeFileSync } from 'node:fs'
+import { availableParallelism } from 'node:os'
+import { parseCalibrateArgs, parseRunArgs, parseSensitivityArgs, parseSweepArgs } from './args'
+import { Agent } from '../model'
+import { mergeParams } from '../params'
+import { runSimulation } from '../run'
+import { historyToCSV, snapshotToCSV } from '../csv'
+import { convergenceStopRule, detectHistoryConvergence } from '../metrics/convergence'
+import { designPoints, validateSpec } from '../sweep/design'
+import { makeJobs } from '../sweep/jobs'
+import { runJobs } from '../sweep/pool'
+import { summarizeResults, tidyHeader, tidyRows } from '../sweep/tidy'
+import { createRng } from '../rng'
+import { SensitivitySpec, indicesToCSV, runSensitivity } from '../sensitivity/analysis'
+import { validateCalibrationSpec } from '../calibration/targets'
+import { particlesToCSV, runCalibration } from '../calibration/calibrate'
+
+const USAGE = `Usage: wealth-abm run [options]
+       wealth-abm sweep --spec <file> [options]
+       wealth-abm sensitivity [--spec <file>] [options]
+       wealth-abm calibrate --targets <file> [options]
+
+run: simulate once and write per-generation metrics as CSV.
+  -p, --params <file>        JSON parameter overrides (defaults for anything left out)
+  -g, --generations <n>      generations to simulate (at most, with --until-converged; default 100)
+  -s, --seed <n>             random seed, overriding the params file
+  -o, --out <file>           metrics CSV (default: stdout)
+  -a, --agents <file>        also write agent snapshots to this CSV
+      --snapshot-every <k>   snapshot every k-th generation (default 0: final only)
+      --until-converged      stop once the --converge-on metrics reach a steady state
+                             (Geweke test, checked every 5 generations from generation
+                             50, and passing until the run is 1.5 times as long), and
+                             report burn-in and steady-state means
+      --converge-on <list>   comma-separated metrics tested for convergence (default gini)
+  -h, --help                 show this message
+
+sweep: run replicates over a grid or Latin hypercube of parameters and write a
+long table of point × replicate × generation × metric.
+      --spec <file>          JSON sweep specification (parameters, design, replicates, ...)
+  -o, --out <file>           tidy CSV (default: stdout)
+      --summary <file>       replicate mean / sd per point, generation and metric
+  -w, --workers <n>          worker threads (default: available cores)
+  -r, --replicates <n>       override the spec's replicates per point
+  -g, --generations <n>      override the spec's generations
+  -s, --seed <n>             override the spec's first seed
+
+sensitivity: Sobol indices (first-order S1, total ST) or Morris elementary
+effects (mu, muStar, sigma) of the steady-state Gini and rank-rank slope, with
+bootstrap confidence intervals.
+      --spec <file>          JSON spec (factors and ranges, outputs, window, ...)
+  -m, --method <name>        sobol (default) or morris
+  -n, --samples <n>          Sobol base samples (runs: n × (factors + 2))
+  -r, --trajectories <n>     Morris trajectories (runs: r × (factors + 1))
+  -g, --generations <n>      generations per run
+  -o, --out <file>           index table CSV (default: stdout)
+  -w, --workers <n>          worker threads (default: available cores)
+  -s, --seed <n>             seed of the design and the runs
+Homophily levers default to [0, 1]. Negative (disassortative) ranges in a spec
+are much slower, since mating then scans every candidate partner.
+
+calibrate: approximate Bayesian computation of the prior parameters given the
+empirical targets; writes weighted posterior samples.
+  -t, --targets <file>       JSON target file (targets, priors, base, generations, window)
+  -m, --method <name>        smc (default) or rejection
+  -n, --particles <n>        posterior sample size (default 100)
+      --draws <n>            rejection: prior draws (default 10 × particles)
+      --populations <n>      smc: populations of decreasing tolerance (default 5)
+  -g, --generations <n>      override the target file's generations
+  -o, --out <file>           posterior CSV (default: stdout)
+  -w, --workers <n>          worker threads (default: available cores)
+  -s, --seed <n>             seed of the calibration
+Default priors are the sensitivity levers, with homophily in [0, 1].
+`
+
+function run(argv: string[]) {
+  const args = parseRunArgs(argv)
+  if (args.help) {
+    process.stdout.write(USAGE)
+    return
+  }
+
+  const overrides = args.params ? JSON.parse(readFileSync(args.params, 'utf8')) : {}
+  const params = mergeParams(overrides)
+  if (args.seed !== undefined) params.seed = args.seed
+
+  if (args.agents) writeFileSync(args.agents, '')
+  const snapshot = (generation: number) => args.agents !== undefined && (
+    generation === args.generations ||
+    (args.snapshotEvery > 0 && generation % args.snapshotEvery === 0)
+  )
+  let wroteHeader = false
+  const writeSnapshot = (pop: Agent[], generation: number) => {
+    appendFileSync(args.agents as string, snapshotToCSV(pop, generation, !wroteHeader))
+    wroteHeader = true
+  }
+  let lastPop: Agent[] = []
+  let lastGeneration = 0
+  const progress = process.stderr.isTTY
+  const converged = convergenceStopRule(args.convergeOn)
+
+  const history = runSimulation(params, {
+    generations: args.generations,
+    onGeneration: (pop, generation) => {
+      if (snapshot(generation)) writeSnapshot(pop, generation)
+      lastPop = pop
+      lastGeneration = generation
+      if (progress) process.stderr.write(`\rGeneration ${generation}/${args.generations}`)
+    },
+    stopWhen: args.untilConverged ? (h, generation) => {
+      const unknown = args.convergeOn.filter(m => !(m in h))
+      if (unknown.length > 0) throw new Error(`Unknown metric for --converge-on: ${unknown.join(', ')}`)
+      return converged(h, generation)
+    } : undefined,
+  })
+  if (progress) process.stderr.write('\n')
+
+  if (args.untilConverged) {
+    // the final snapshot of a run that stopped early
+    if (args.agents !== undefined && !snapshot(lastGeneration)) writeSnapshot(lastPop, lastGeneration)
+    const results = detectHistoryConvergence(history, args.convergeOn)
+    process.stderr.write(`Stopped after generation ${lastGeneration}\n`)
+    Object.entries(results).forEach(([metric, r]) => process.stderr.write(r.converged
+      ? `${metric}: burn-in ${r.burnIn}, steady state ${r.mean.toPrecision(4)} [${r.lo.toPrecision(4)}, ${r.hi.toPrecision(4)}]\n`
+      : `${metric}: not converged (Geweke z ${r.z.toFixed(2)})\n`
+    ))
+  }
+
+  const csv = historyToCSV(history)
+  if (args.out) writeFileSync(args.out, csv)
+  else process.stdout.write(csv)
+}
+
+async function sweep(argv: string[]) {
+  const args = parseSweepArgs(argv)
+  if (args.help) {
+    process.stdout.write(USAGE)
+    return
+  }
+  if (!args.spec) throw new Error('sweep needs --spec <file>')
+
+  const spec = validateSpec(JSON.parse(readFileSync(args.spec, 'utf8')))
+  if (args.replicates !== undefined) spec.replicates = args.replicates
+  if (args.generations !== undefined) spec.generations = args.generations
+  if (args.seed !== undefined) spec.seed = args.seed
+
+  const points = designPoints(spec, createRng(spec.seed ?? 1))
+  const paramNames = Object.keys(spec.parameters)
+  const jobs = makeJobs(spec, points)
+  const write = (text: string) => args.out ? appendFileSync(args.out, text) : process.stdout.write(text)
+  const progress = process.stderr.isTTY
+  let done = 0
+
+  if (args.out) writeFileSync(args.out, '')
+  write(tidyHeader(paramNames))
+  const results = await runJobs(jobs, {
+    workers: args.workers ?? availableParallelism(),
+    workerUrl: new URL('./worker.mjs', import.meta.url),
+    onResult: result => {
+      write(tidyRows(result, points[result.point], paramNames))
+      done += 1
+      if (progress) process.stderr.write(`\rRun ${done}/${jobs.length}`)
+    },
+  })
+  if (progress) process.stderr.write('\n')
+
+  if (args.summary) writeFileSync(args.summary, summarizeResults(results, points, paramNames))
+}
+
+async function sensitivity(argv: string[]) {
+  const args = parseSensitivityArgs(argv)
+  if (args.help) {
+    process.stdout.write(USAGE)
+    return
+  }
+
+  const spec: SensitivitySpec = args.spec ? JSON.parse(readFileSync(args.spec, 'utf8')) : {}
+  if (args.method !== undefined) spec.method = args.method
+  if (args.samples !== undefined) spec.samples = args.samples
+  if (args.trajectories !== undefined) spec.trajectories = args.trajectories
+  if (args.generations !== undefined) spec.generations = args.generations
+  if (args.seed !== undefined) spec.seed = args.seed
+  const progress = process.stderr.isTTY
+
+  const rows = await runSensitivity(spec, {
+    workers: args.workers ?? availableParallelism(),
+    workerUrl: new URL('./worker.mjs', import.meta.url),
+    onProgress: (done, total) => { if (progress) process.stderr.write(`\rRun ${done}/${total}`) },
+  })
+  if (progress) process.stderr.write('\n')
+
+  const csv = indicesToCSV(rows)
+  if (args.out) writeFileSync(args.out, csv)
+  else process.stdout.write(csv)
+}
+
+async function calibrate(argv: string[]) {
+  const args = parseCalibrateArgs(argv)
+  if (args.help) {
+    process.stdout.write(USAGE)
+    return
+  }
+  if (!args.targets) throw new Error('calibrate needs --targets <file>')
+
+  const spec = validateCalibrationSpec(JSON.parse(readFileSync(args.targets, 'utf8')))
+  if (args.generations !== undefined) spec.generations = args.generations
+
+  const particles = await runCalibration(spec, {
+    method: args.method,
+    particles: args.particles,
+    draws: args.draws,
+    populations: args.populations,
+    seed: args.seed,
+    onPopulation: (t, epsilon, acceptance) => process.stderr.write(
+      `Population ${t}: tolerance ${epsilon.toPrecision(3)}, acceptance ${(acceptance * 100).toFixed(1)}%\n`
+    ),
+  }, {
+    workers: args.workers ?? availableParallelism(),
+    workerUrl: new URL('./worker.mjs', import.meta.url),
+  })
+
+  const csv = particlesToCSV(particles, Object.keys(spec.priors ?? {}), Object.keys(spec.targets))
+  if (args.out) writeFileSync(args.out, csv)
+  else process.stdout.write(csv)
+}
+
+export async function main(argv: string[]): Promise<number> {
+  const [command, ...rest] = argv
+  try {
+    switch (command) {
+      case 'run':
+        run(rest)
+        return 0
+      case 'sweep':
+        await sweep(rest)
+        return 0
+      case 'sensitivity':
+        await sensitivity(rest)
+        return 0
+      case 'calibrate':
+        await calibrate(rest)
+        return 0
+      case undefined:
+      case 'help':
+      case '--help':
+      case '-h':
+        process.stdout.write(USAGE)
+        return 0
+      default:
+        process.stderr.write(`Unknown command '${command}'\n\n${USAGE}`)
+        return 1
+    }
+  } catch (err) {
+    process.stderr.write(`wealth-abm: ${(err as Error).message}\n`)
+    return 1
+  }
+}
+
+main(process.argv.slice(2)).then(code => { process.exitCode = code })
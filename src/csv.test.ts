@@ -0,0 +1,36 @@
+// src/csv.test.ts
+import { describe, it, expect } from 'vitest'
+import { historyToCSV, snapshotToCSV } from './csv'
+import type { Agent } from './model'
+
+const agent: Agent = {
+  id: 3, alleles: [0.1, 0.3], meanAllele: 0.2, env: -0.5, rawenv: -0.5,
+  educationScore: 1.5, wealth: 1000, parentWealth: 2000, parents: null,
+}
+
+describe('CSV Output - csv.ts', () => {
+  describe('historyToCSV', () => {
+    it('should write one row per step', () => {
+      const csv = historyToCSV({ gini: [0.3, 0.35], ige: [NaN, 0.5] })
+      expect(csv).toBe('generation,gini,ige\n0,0.3,\n1,0.35,0.5\n')
+    })
+
+    it('should use the given index name', () => {
+      expect(historyToCSV({ gini: [0.3] }, 'year')).toBe('year,gini\n0,0.3\n')
+    })
+  })
+
+  describe('snapshotToCSV', () => {
+    it('should write a header and one row per agent', () => {
+      const lines = snapshotToCSV([agent, { ...agent, id: 4, catastrophe: true }], 7).trim().split('\n')
+      expect(lines).toHaveLength(3)
+      expect(lines[0]).toBe('generation,id,meanAllele,polygenicScore,env,educationScore,wealth,parentWealth,inheritance,mutations,catastrophe')
+      expect(lines[1]).toBe('7,3,0.2,,-0.5,1.5,1000,2000,,,')
+      expect(lines[2].endsWith(',1')).toBe(true)
+    })
+
+    it('should omit the header when appending', () => {
+      expect(snapshotToCSV([agent], 1, false).startsWith('1,3,')).toBe(true)
+    })
+  })
+})
@@ -0,0 +1,39 @@
+// src/csv.ts
+// CSV output for per-generation metrics and agent snapshots.
+import type { Agent } from './model'
+import { History, historyLength } from './metrics/history'
+
+/** One CSV cell: NaN and missing values are left empty, text is quoted if needed */
+function cell(value: unknown): string {
+  if (value === undefined || value === null) return ''
+  if (typeof value === 'number') return Number.isFinite(value) ? String(value) : ''
+  if (typeof value === 'boolean') return value ? '1' : '0'
+  const text = String(value)
+  return /[",\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text
+}
+
+/** One CSV line */
+export const csvRow = (values: unknown[]) => values.map(cell).join(',') + '\n'
+
+/** Wide table: one row per step, one column per metric */
+export function historyToCSV(history: History, index = 'generation'): string {
+  const keys = Object.keys(history)
+  let csv = csvRow([index, ...keys])
+  for (let i = 0; i < historyLength(history); i++) {
+    csv += csvRow([i, ...keys.map(k => history[k][i])])
+  }
+  return csv
+}
+
+/** Agent fields written to snapshots, in column order */
+export const SNAPSHOT_COLUMNS: (keyof Agent)[] = [
+  'id', 'meanAllele', 'polygenicScore', 'env', 'educationScore',
+  'wealth', 'parentWealth', 'inheritance', 'mutations', 'catastrophe',
+]
+
+/** Long table of agents at one generation; pass header = false to append */
+export function snapshotToCSV(pop: Agent[], generation: number, header = true): string {
+  let csv = header ? csvRow(['generation', ...SNAPSHOT_COLUMNS]) : ''
+  pop.forEach(a => { csv += csvRow([generation, ...SNAPSHOT_COLUMNS.map(k => a[k])]) })
+  return csv
+}
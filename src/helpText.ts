@@ -49,10 +49,13 @@ export const helpSections: HelpSection[] = [
         <li><strong>Initial population</strong>: most parameters are drawn directly from a Gaussian distribution. Agent properties then evolve with each generation from there.</li>
         <li><strong>Genes</strong>: each agent inherits one randomly chosen "education" gene from each parent, with occasional mutation.</li>
         <li><strong>Environment</strong>: determined by the sum of parents' wealth, with optional noise added in to account for chance.</li>
-        <li><strong>Education</strong>: is a function of the agent's average gene value (they inherit 2 alleles), and the environment value, weighted by the gene-environment proportion slider.</li>
+        <li><strong>Education</strong>: is a function of the agent's polygenic score and the environment value, weighted by the gene-environment proportion slider. The score sums many diploid loci (one by default, where it is simply the average of the 2 alleles), with optional dominance; each parent passes on one recombined haplotype. Optional extras: a gene × environment interaction, non-shared noise, and passive gene–environment correlation, where the parents' genes shift the environment they provide.</li>
         <li><strong>Wealth</strong>: is a function of the agent's education success and parental wealth, weighted by the education-parental-wealth proportion slider.</li>
-        <li><strong>Mating Logic</strong>: random or assortative by genes/environment controlled by homophily sliders.</li>
-        <li><strong>Inter-generational wealth catastrophe</strong>: there is a small chance that some or all wealth will be lost between generations.</li>
+        <li><strong>Accumulation Model</strong> (optional): instead of redrawing wealth, agents start from their inheritance, earn labor income (from education) and capital income (return on capital &times; previous wealth), and add the saved share of that income to their wealth. The share saved depends on where the agent's inherited wealth ranks: by default the bottom 20% save 5%, the middle 60% save 15%, the next 19% save 40%, and the top 1% save 70%. Each generation lives a configurable number of years, and income, capital returns and savings compound year by year before the next generation inherits.</li>
+        <li><strong>Fertility</strong>: each pair has two children by default; family sizes can instead be random and can rise or fall with the pair's wealth rank and education, letting the population grow or shrink unless it is capped.</li>
+        <li><strong>Mating Logic</strong>: random, assortative or (with negative homophily) disassortative by genes, environment, education and wealth, controlled by one homophily slider per trait. Pairs are monogamous (a legacy sampled mode lets one agent mate several times); only the partnership rate of agents enter the marriage market, and the rest stay single, having children alone or none at all.</li>
+        <li><strong>Inheritance</strong>: the parents' combined wealth is divided among their children (equally, all to the eldest, or weighted by birth order), and each child receives the inheritance rate times their share.</li>
+        <li><strong>Inter-generational wealth catastrophe</strong>: with the catastrophe rate, some or all of an inheritance is lost between generations; the severity sets the average share lost. The default reproduces the original model, where every inheritance is scaled by a random draw that usually wipes out most of it.</li>
       </ul>
     `
   },
@@ -64,8 +67,11 @@ export const helpSections: HelpSection[] = [
       <li><strong>Population Raster (center)</strong>: grid of agents colored by the selected attribute, always ordered by wealth.</li>
       <li><strong>Histogram</strong>: distribution of genes, environment, education, or wealth. Wealth is log transformed. </li>
       <li><strong>Lorenz Curve</strong>: cumulative share of agents vs. cumulative share of wealth. Perfect wealth equality is a diagonal line.</li>
-      <li><strong>Gini Coefficient</strong>: summary statistic (0&ndash;1) of wealth inequality.</li>
-      <li><strong>Time Series</strong>: tracks Gini across generations.</li>
+      <li><strong>Gini Coefficient</strong>: summary statistic (0&ndash;1) of wealth inequality, computed exactly from sorted wealth. With debts it is normalized by total absolute wealth, so it stays between 0 and 1. Once the Gini has settled (a Geweke test of at least 20 generations), the status line shows its steady-state mean &plusmn; a 95% band and the generation it settled from.</li>
+      <li><strong>Metrics Over Time</strong>: plots any set of measures across generations, with a legend and hover tooltips. Choose from the Gini, top 1% / top 10% / bottom 50% wealth shares, Theil T and L, Atkinson indices, the Palma ratio (top 10% over bottom 40%), P90/P10, the Hill estimate of the Pareto tail exponent (smaller = fatter tail), mobility measures, mean education, environment and polygenic score, and more. Untick <em>Shared y axis</em> to give each measure its own scale; with two measures they are labeled on the left and right axes, and with more each is drawn as a share of its own range, with the legend giving the value at the bottom and top of the plot. Under the accumulation model the wealth measures can also be shown year by year.</li>
+      <li><strong>Nature vs. Nurture</strong>: each generation, the shares of education variance attributable to genes, environment, parental wealth and noise (regressing education on the polygenic score, environment and log parental wealth together), and the slopes of children's polygenic score, environment, education and log wealth on their parents' average. The same split of log-wealth variance can be picked under <em>Metrics Over Time</em>.</li>
+      <li><strong>Mobility</strong>: the rank-rank slope of children's wealth percentile on their parents' (0 = full mobility, 1 = rank fully inherited); the status bar also shows the intergenerational elasticity (IGE) of wealth.</li>
+      <li><strong>Quintile Transitions</strong>: heatmap of the chance that a child born into each parental wealth quintile ends up in each wealth quintile, for the current generation or averaged over recent ones. Perfect mobility gives 20% everywhere; a bright diagonal means wealth rank is inherited.</li>
       </ul>
     `
   },
@@ -125,7 +131,6 @@ export const helpSections: HelpSection[] = [
         <li>Redistribution Schemes: apply progressive/regressive taxes to final wealth and observe Gini changes.</li>
         <li>Universal Basic Endowment: give every newborn a fixed "starter wealth" or "starter education credit" before inheritance and education calculations.</li>
         <li>Environmental Shock: introduce a one-time large Gaussian noise spike in Env for a random subset of agents (e.g., disasters, pandemics).</li>
-        <li>Negative Homophily: invert assortative mating logic so agents deliberately pair with those farthest from them in gene/env space.</li>
       </ul>
 
     `
@@ -5,15 +5,25 @@ import {
   defaultParams,
   initializePopulation,
   nextGeneration,
+  computePairingStats,
+  PairingStats,
+  mutationCount,
 } from './model'
+import { Rng, createRng } from './rng'
+import { variance } from './metrics/stats'
+import { computeTransitionMatrix } from './metrics/mobility'
+import { averageMatrices, drawTransitionHeatmap } from './viz/heatmap'
+import { History, appendRecord, clearHistory } from './metrics/history'
+import { generationRecord, wealthRecord } from './metrics/record'
+import { detectConvergence } from './metrics/convergence'
 import {
   prepareRasterArray,
   drawRasterCanvas,
 } from './viz/raster'
 import {
   drawLorenzCurve,
-  drawGiniTimeSeries,
-  computeGini,
+  drawTimeSeries,
+  TimeSeries,
   drawHistogram,
 } from './viz/plots'
 
@@ -54,12 +64,21 @@ document.addEventListener("DOMContentLoaded", () => {
 // **Use deep‐clone on defaultParams** so homophily is always an object
 let params: Params = {
   ...defaultParams,
-  homophily: { ...defaultParams.homophily }
+  homophily: { ...defaultParams.homophily },
+  fertility: { ...defaultParams.fertility },
+  genetics: { ...defaultParams.genetics },
 }
 
 let population: Agent[] = []
-let historyGini: number[] = []
+let rng: Rng = createRng(params.seed)
+// every plotted metric keyed as in SERIES, one entry per generation (per year
+// for the wealth measures in historyAnnual)
+const history: History = {}
+const historyAnnual: History = {}
+let historyTransitions: number[][][] = []
+let pairingStats: PairingStats | null = null
 let generation = 0
+let year = 0
 let isRunning = false
 const frameDelay = 750 // ms between generations
 
@@ -69,10 +88,21 @@ const pauseButton    = document.getElementById('pause-btn')    as HTMLButtonElem
 const resetButton    = document.getElementById('reset-btn')    as HTMLButtonElement
 const featureSelect  = document.getElementById('feature-select') as HTMLSelectElement
 const stepBtn       = document.getElementById('step-btn')      as HTMLButtonElement
+const seedInput     = document.getElementById('seed-input')    as HTMLInputElement
 const statusEl      = document.getElementById('status')        as HTMLElement
+const giniResolution = document.getElementById('gini-resolution') as HTMLSelectElement
+const heatmapWindow  = document.getElementById('heatmap-window')  as HTMLSelectElement
+const seriesMetric   = document.getElementById('series-metric')   as HTMLSelectElement
+const sharedScaleToggle = document.getElementById('toggle-shared-scale') as HTMLInputElement
 
 const sliderGeneEnv  = document.getElementById('slider-gene-env')  as HTMLInputElement
 const labelGeneEnv   = document.getElementById('label-gene-env')   as HTMLElement
+const sliderGxE      = document.getElementById('slider-gxe')       as HTMLInputElement
+const labelGxE       = document.getElementById('label-gxe')        as HTMLElement
+const sliderEduNoise = document.getElementById('slider-edu-noise') as HTMLInputElement
+const labelEduNoise  = document.getElementById('label-edu-noise')  as HTMLElement
+const sliderPassiveRGE = document.getElementById('slider-passive-rge') as HTMLInputElement
+const labelPassiveRGE  = document.getElementById('label-passive-rge')  as HTMLElement
 const sliderEnvNoise = document.getElementById('slider-env-noise')as HTMLInputElement
 const labelEnvNoise  = document.getElementById('label-env-noise') as HTMLElement
 const sliderFinWeight   = document.getElementById('slider-fin-weight')  as HTMLInputElement
@@ -83,6 +113,69 @@ const sliderHomGene  = document.getElementById('slider-hom-gene')as HTMLInputEle
 const labelHomGene   = document.getElementById('label-hom-gene') as HTMLElement
 const sliderHomEnv   = document.getElementById('slider-hom-env')  as HTMLInputElement
 const labelHomEnv    = document.getElementById('label-hom-env')   as HTMLElement
+const sliderLoci     = document.getElementById('slider-loci') as HTMLInputElement
+const labelLoci      = document.getElementById('label-loci')  as HTMLElement
+const sliderDominance = document.getElementById('slider-dominance') as HTMLInputElement
+const labelDominance  = document.getElementById('label-dominance')  as HTMLElement
+const sliderRecombination = document.getElementById('slider-recombination') as HTMLInputElement
+const labelRecombination  = document.getElementById('label-recombination')  as HTMLElement
+const sliderMutationRate = document.getElementById('slider-mutation-rate') as HTMLInputElement
+const labelMutationRate  = document.getElementById('label-mutation-rate')  as HTMLElement
+const mutationKernelSelect = document.getElementById('mutation-kernel') as HTMLSelectElement
+const sliderMutationStd  = document.getElementById('slider-mutation-std') as HTMLInputElement
+const labelMutationStd   = document.getElementById('label-mutation-std')  as HTMLElement
+const sliderHomEdu   = document.getElementById('slider-hom-edu')  as HTMLInputElement
+const labelHomEdu    = document.getElementById('label-hom-edu')   as HTMLElement
+const sliderHomWealth = document.getElementById('slider-hom-wealth') as HTMLInputElement
+const labelHomWealth  = document.getElementById('label-hom-wealth')  as HTMLElement
+const sliderHomParentWealth = document.getElementById('slider-hom-parent-wealth') as HTMLInputElement
+const labelHomParentWealth  = document.getElementById('label-hom-parent-wealth')  as HTMLElement
+const toggleAccumulation = document.getElementById('toggle-accumulation') as HTMLInputElement
+const sliderCapitalReturn = document.getElementById('slider-capital-return') as HTMLInputElement
+const labelCapitalReturn  = document.getElementById('label-capital-return')  as HTMLElement
+const sliderPeriods     = document.getElementById('slider-periods')      as HTMLInputElement
+const labelPeriods      = document.getElementById('label-periods')       as HTMLElement
+const sliderReturnVol   = document.getElementById('slider-return-vol')   as HTMLInputElement
+const labelReturnVol    = document.getElementById('label-return-vol')    as HTMLElement
+const sliderInheritance = document.getElementById('slider-inheritance-rate') as HTMLInputElement
+const labelInheritance  = document.getElementById('label-inheritance-rate')  as HTMLElement
+const sliderCatRate     = document.getElementById('slider-catastrophe-rate') as HTMLInputElement
+const labelCatRate      = document.getElementById('label-catastrophe-rate')  as HTMLElement
+const sliderCatSeverity = document.getElementById('slider-catastrophe-severity') as HTMLInputElement
+const labelCatSeverity  = document.getElementById('label-catastrophe-severity')  as HTMLElement
+const catDistSelect     = document.getElementById('catastrophe-distribution') as HTMLSelectElement
+const divisionSelect    = document.getElementById('inheritance-division') as HTMLSelectElement
+const sliderInheritDecay = document.getElementById('slider-inheritance-decay') as HTMLInputElement
+const labelInheritDecay  = document.getElementById('label-inheritance-decay')  as HTMLElement
+const sliderPartnership = document.getElementById('slider-partnership') as HTMLInputElement
+const labelPartnership  = document.getElementById('label-partnership')  as HTMLElement
+const matingModeSelect  = document.getElementById('mating-mode') as HTMLSelectElement
+const sliderSingleFertility = document.getElementById('slider-single-fertility') as HTMLInputElement
+const labelSingleFertility  = document.getElementById('label-single-fertility')  as HTMLElement
+const fertilityModelSelect = document.getElementById('fertility-model') as HTMLSelectElement
+const sliderFertilityMean  = document.getElementById('slider-fertility-mean') as HTMLInputElement
+const labelFertilityMean   = document.getElementById('label-fertility-mean')  as HTMLElement
+const sliderFertilityWealth = document.getElementById('slider-fertility-wealth') as HTMLInputElement
+const labelFertilityWealth  = document.getElementById('label-fertility-wealth')  as HTMLElement
+const sliderFertilityEdu   = document.getElementById('slider-fertility-edu') as HTMLInputElement
+const labelFertilityEdu    = document.getElementById('label-fertility-edu')  as HTMLElement
+const toggleCapPopulation  = document.getElementById('toggle-cap-population') as HTMLInputElement
+const sliderWageRate    = document.getElementById('slider-wage-rate')    as HTMLInputElement
+const labelWageRate     = document.getElementById('label-wage-rate')     as HTMLElement
+const sliderSavingsBottom = document.getElementById('slider-savings-bottom') as HTMLInputElement
+const labelSavingsBottom  = document.getElementById('label-savings-bottom')  as HTMLElement
+const sliderSavingsMiddle = document.getElementById('slider-savings-middle') as HTMLInputElement
+const labelSavingsMiddle  = document.getElementById('label-savings-middle')  as HTMLElement
+const sliderSavingsUpper  = document.getElementById('slider-savings-upper')  as HTMLInputElement
+const labelSavingsUpper   = document.getElementById('label-savings-upper')   as HTMLElement
+const sliderSavingsTop    = document.getElementById('slider-savings-top')    as HTMLInputElement
+const labelSavingsTop     = document.getElementById('label-savings-top')     as HTMLElement
+const sliderCutoffBottom  = document.getElementById('slider-cutoff-bottom')  as HTMLInputElement
+const labelCutoffBottom   = document.getElementById('label-cutoff-bottom')   as HTMLElement
+const sliderCutoffMiddle  = document.getElementById('slider-cutoff-middle')  as HTMLInputElement
+const labelCutoffMiddle   = document.getElementById('label-cutoff-middle')   as HTMLElement
+const sliderCutoffUpper   = document.getElementById('slider-cutoff-upper')   as HTMLInputElement
+const labelCutoffUpper    = document.getElementById('label-cutoff-upper')    as HTMLElement
 
 
 const canvas = document.getElementById('raster') as HTMLCanvasElement
@@ -93,6 +186,11 @@ canvas.height = 400
 
 const lorenzSvg = d3.select('#lorenz') as d3.Selection<SVGSVGElement, unknown, null, undefined>
 const giniSvg   = d3.select('#gini')   as d3.Selection<SVGSVGElement, unknown, null, undefined>
+const catastropheSvg = d3.select('#catastrophe') as d3.Selection<SVGSVGElement, unknown, null, undefined>
+const geneShareSvg = d3.select('#gene-share') as d3.Selection<SVGSVGElement, unknown, null, undefined>
+const poSlopeSvg   = d3.select('#po-slope')   as d3.Selection<SVGSVGElement, unknown, null, undefined>
+const rankRankSvg  = d3.select('#rank-rank')  as d3.Selection<SVGSVGElement, unknown, null, undefined>
+const heatmapSvg   = d3.select('#transition-heatmap') as d3.Selection<SVGSVGElement, unknown, null, undefined>
 const histSvg = d3.select('#histogram') as d3.Selection<SVGSVGElement, unknown, null, undefined>
 const histWealthSvg = d3.select('#histogram-wealth') as d3.Selection<SVGSVGElement, unknown, null, undefined>
 const histTitle = document.getElementById('hist-title')     as HTMLElement
@@ -123,11 +221,53 @@ function bindSlider(
         break;
       case 'homEnv':
       case 'homGene':
-        label.textContent = `Random ${((1 - v)*100).toFixed(0)}% / ${(v*100).toFixed(0)}% Homophily`;
+        label.textContent = `Random ${((1 - Math.abs(v))*100).toFixed(0)}% / ${(Math.abs(v)*100).toFixed(0)}% ${v < 0 ? 'Heterophily' : 'Homophily'}`;
         break;
       case 'envNoise':
+      case 'eduNoise':
       case 'finNoise':
+      case 'returnVol':
+      case 'mutationStd':
         label.textContent = `Normal(0, ${v.toFixed(2)})`;
+        break;
+      case 'capitalReturn':
+      case 'savingsRate':
+      case 'rate':
+        label.textContent = `${(v*100).toFixed(1)}%`;
+        break;
+      case 'fertilityMean':
+        label.textContent = `${v.toFixed(1)} children per pair`;
+        break;
+      case 'gradient':
+        label.textContent = `${v > 0 ? '+' : ''}${v.toFixed(2)} log fertility`;
+        break;
+      case 'relativeFertility':
+        label.textContent = `${(v*100).toFixed(0)}% of a pair's children`;
+        break;
+      case 'inheritDecay':
+        label.textContent = `×${v.toFixed(2)} per younger sibling`;
+        break;
+      case 'wageRate':
+        label.textContent = `×${v.toFixed(2)}`;
+        break;
+      case 'periods':
+        label.textContent = `${v.toFixed(0)} year${v === 1 ? '' : 's'}`;
+        break;
+      case 'gxe':
+        label.textContent = `${v > 0 ? '+' : ''}${v.toFixed(2)} × genes × env`;
+        break;
+      case 'passiveRGE':
+        label.textContent = v === 0 ? 'Off' : `+${v.toFixed(2)} env per unit of parental genes`;
+        break;
+      case 'loci':
+        label.textContent = `${v.toFixed(0)} loc${v === 1 ? 'us' : 'i'}`;
+        break;
+      case 'dominance':
+        label.textContent = v === 0 ? 'Additive' : `${(Math.abs(v)*100).toFixed(0)}% toward the ${v > 0 ? 'higher' : 'lower'} allele`;
+        break;
+      case 'savingsCutoff':
+        label.textContent = `P${(v*100).toFixed(0)}`;
+        break;
       default:
         break;
     }
@@ -149,28 +289,93 @@ const colorScales: Record<FeatureKey, d3.ScaleSequential<string>> = {
   parentWealth: d3.scaleSequential(d3.interpolateMagma),
 }
 
+/** Metrics that can be plotted over time; shares and Atkinson indices live in [0, 1] */
+const SERIES: Record<string, { label: string, domain?: [number, number] }> = {
+  gini:                    { label: 'Gini', domain: [0, 1] },
+  top1:                    { label: 'Top 1% share', domain: [0, 1] },
+  top10:                   { label: 'Top 10% share', domain: [0, 1] },
+  bottom50:                { label: 'Bottom 50% share', domain: [0, 1] },
+  theilT:                  { label: 'Theil T' },
+  theilL:                  { label: 'Theil L' },
+  atkinson05:              { label: 'Atkinson (ε = 0.5)', domain: [0, 1] },
+  atkinson1:               { label: 'Atkinson (ε = 1)', domain: [0, 1] },
+  atkinson2:               { label: 'Atkinson (ε = 2)', domain: [0, 1] },
+  palma:                   { label: 'Palma ratio' },
+  p90p10:                  { label: 'P90/P10' },
+  hill:                    { label: 'Pareto tail exponent (Hill)' },
+  ige:                     { label: 'IGE' },
+  rankRankSlope:           { label: 'Rank-rank slope' },
+  meanWealth:              { label: 'Mean wealth' },
+  meanEducation:           { label: 'Mean education score' },
+  meanEnv:                 { label: 'Mean environment' },
+  meanPGS:                 { label: 'Mean polygenic score' },
+  varPGS:                  { label: 'Var(polygenic score)' },
+  mutations:               { label: 'New mutations' },
+  geneShare:               { label: 'Education variance from genes' },
+  eduEnvShare:             { label: 'Education variance from environment' },
+  eduParentWealthShare:    { label: 'Education variance from parental wealth' },
+  eduNoiseShare:           { label: 'Education variance from noise' },
+  wealthGeneShare:         { label: 'Log-wealth variance from genes' },
+  wealthEnvShare:          { label: 'Log-wealth variance from environment' },
+  wealthParentWealthShare: { label: 'Log-wealth variance from parental wealth' },
+  wealthNoiseShare:        { label: 'Log-wealth variance from noise' },
+  poSlope:                 { label: 'Parent–offspring slope (education)' },
+  poSlopeGene:             { label: 'Parent–offspring slope (polygenic score)' },
+  poSlopeEnv:              { label: 'Parent–offspring slope (environment)' },
+  poSlopeWealth:           { label: 'Parent–offspring slope (log wealth)' },
+  catastrophe:             { label: 'Dynasties struck by catastrophe', domain: [0, 1] },
+  partnered:               { label: 'Partnered share', domain: [0, 1] },
+}
+
+/** One history entry as a chart series */
+function seriesFor(key: string, source: History = history): TimeSeries {
+  return { name: SERIES[key].label, values: source[key] ?? [], domain: SERIES[key].domain }
+}
+
 // Core routines
 function reset() {
   // **Deep-clone here too** 
   params = {
     ...defaultParams,
-    homophily: { ...defaultParams.homophily }
+    homophily: { ...defaultParams.homophily },
+    fertility: { ...defaultParams.fertility },
+    // the loci count only matters for a fresh population, so keep the slider's choice
+    genetics: { ...defaultParams.genetics, nLoci: parseInt(sliderLoci.value) },
   }
-  population = initializePopulation(params)
-  historyGini.length = 0
+  // a blank or malformed seed leaves the run unseeded
+  const seed = parseInt(seedInput.value)
+  if (Number.isInteger(seed) && seed >= 0) params.seed = seed
+  rng = createRng(params.seed)
+  population = initializePopulation(params, rng)
+  clearHistory(history)
+  clearHistory(historyAnnual)
+  historyTransitions = []
+  pairingStats = null
   generation = 0
+  year = 0
 
-  // seed an initial Gini so drawGiniTimeSeries never sees an empty array
-  const initialWealth = population.map(a => a.wealth)
-  historyGini.push(computeGini(initialWealth))
+  // generation 0 is the founders
+  appendRecord(history, generationRecord(population))
+  appendRecord(historyAnnual, wealthRecord(population.map(a => a.wealth)))
   
   draw()
 }
 
 function tick() {
-  population = nextGeneration(population, params)
-  const wealthArray = population.map(a => a.wealth)
-  historyGini.push(computeGini(wealthArray))
+  population = nextGeneration(population, params, rng, {
+    onPeriod: pop => {
+      year += 1
+      if (pop.length > 0) appendRecord(historyAnnual, wealthRecord(pop.map(a => a.wealth)))
+    },
+    onPairing: (pairs, pop) => { pairingStats = computePairingStats(pop, pairs) },
+  })
+  if (population.length === 0) {
+    isRunning = false
+    statusEl.textContent = `Gen: ${generation + 1} | The population died out. Reset to start again.`
+    return
+  }
+  appendRecord(history, generationRecord(population, pairingStats))
+  historyTransitions.push(computeTransitionMatrix(population))
   generation += 1
   draw()
   if (isRunning) setTimeout(tick, frameDelay)
@@ -191,7 +396,23 @@ function draw() {
 
   // 2) Lorenz & Gini
   drawLorenzCurve(lorenzSvg, population)
-  drawGiniTimeSeries(giniSvg, historyGini)
+  // only the wealth measures are recorded year by year
+  const annual = giniResolution.value === 'year'
+  const selected = Array.from(seriesMetric.selectedOptions, o => o.value)
+    .filter(key => !annual || key in historyAnnual)
+  drawTimeSeries(
+    giniSvg,
+    selected.map(key => seriesFor(key, annual ? historyAnnual : history)),
+    { sharedScale: sharedScaleToggle.checked, xLabel: annual ? 'Year' : 'Generation' }
+  )
+  drawTimeSeries(catastropheSvg, [seriesFor('catastrophe')])
+  drawTimeSeries(geneShareSvg, ['geneShare', 'eduEnvShare', 'eduParentWealthShare', 'eduNoiseShare'].map(key => seriesFor(key)))
+  drawTimeSeries(poSlopeSvg, ['poSlopeGene', 'poSlopeEnv', 'poSlope', 'poSlopeWealth'].map(key => seriesFor(key)))
+  drawTimeSeries(rankRankSvg, [seriesFor('rankRankSlope')])
+  drawTransitionHeatmap(
+    heatmapSvg,
+    averageMatrices(historyTransitions, parseInt(heatmapWindow.value))
+  )
 
   // 3) Histogram
   histTitle.textContent = `Distribution of Selected Feature: ${featureKey}`
@@ -209,10 +430,30 @@ function draw() {
   )
 
   // 4) Status
-  const latestG = historyGini[historyGini.length - 1] ?? 0
-  statusEl.textContent = `Gen: ${generation} | Gini: ${latestG.toFixed(2)} | Pop: ${population.length} | Feature: ${featureKey}`
+  const latestG = history.gini?.[history.gini.length - 1] ?? 0
+  const pairingText = pairingStats
+    ? ` | Partnered: ${(pairingStats.fractionPartnered * 100).toFixed(0)}% | Spousal r(wealth): ${pairingStats.spousalCorrelation.wealth.toFixed(2)}`
+    : ''
+  const geneticVariance = variance(population.map(a => a.polygenicScore ?? a.meanAllele))
+  const latestIGE = history.ige?.[history.ige.length - 1]
+  const mobilityText = latestIGE !== undefined && Number.isFinite(latestIGE) ? ` | IGE: ${latestIGE.toFixed(2)}` : ''
+  const geneticsText = ` | Mutations: ${mutationCount(population)} | Var(PGS): ${geneticVariance.toFixed(3)}`
+  const steady = detectConvergence(history.gini ?? [])
+  const steadyText = steady.converged
+    ? ` (steady ${steady.mean.toFixed(2)} ± ${((steady.hi - steady.lo) / 2).toFixed(2)} from gen ${steady.burnIn})`
+    : ''
+  statusEl.textContent = `Gen: ${generation} | Year: ${year} | Gini: ${latestG.toFixed(2)}${steadyText} | Pop: ${population.length}${mobilityText}${pairingText}${geneticsText} | Feature: ${featureKey}`
 }
 
+// time-series choices, Gini selected by default
+Object.entries(SERIES).forEach(([key, { label }]) => {
+  const opt = document.createElement('option')
+  opt.value = key
+  opt.textContent = label
+  opt.selected = key === 'gini'
+  seriesMetric.append(opt)
+})
+
 startButton.addEventListener('click', () => {
   if (!isRunning) { isRunning = true; tick() }
 })
@@ -220,14 +461,70 @@ pauseButton.addEventListener('click', () => { isRunning = false })
 resetButton.addEventListener('click', () => { isRunning = false; reset() })
 stepBtn.addEventListener('click', () => { if (!isRunning) tick() })
 featureSelect.addEventListener('change', draw)
+giniResolution.addEventListener('change', draw)
+seriesMetric.addEventListener('change', draw)
+sharedScaleToggle.addEventListener('change', draw)
+heatmapWindow.addEventListener('change', draw)
 
 // slider bindings
 bindSlider(sliderGeneEnv, labelGeneEnv, 'geneEnv', v => params.geneEnvWeight    = v)
+bindSlider(sliderGxE, labelGxE, 'gxe', v => params.gxeWeight = v)
+bindSlider(sliderEduNoise, labelEduNoise, 'eduNoise', v => params.educationNoiseStd = v)
+bindSlider(sliderPassiveRGE, labelPassiveRGE, 'passiveRGE', v => params.passiveRGE = v)
 bindSlider(sliderEnvNoise, labelEnvNoise, 'envNoise', v => params.envNoiseStd        = v)
 bindSlider(sliderFinWeight, labelFinWeight, 'finWeight', v => params.financeWeight     = v)
 bindSlider(sliderFinNoise, labelFinNoise, 'finNoise', v => params.financeNoise      = v)
 bindSlider(sliderHomGene, labelHomGene, 'homGene', v => params.homophily.gene    = v)
 bindSlider(sliderHomEnv, labelHomEnv, 'homEnv', v => params.homophily.env     = v)
+bindSlider(sliderLoci, labelLoci, 'loci', v => params.genetics.nLoci = v)
+bindSlider(sliderDominance, labelDominance, 'dominance', v => params.genetics.dominance = v)
+bindSlider(sliderRecombination, labelRecombination, 'rate', v => params.genetics.recombinationRate = v)
+bindSlider(sliderMutationRate, labelMutationRate, 'rate', v => params.genetics.mutationRate = v)
+mutationKernelSelect.addEventListener('change', () => {
+  params.genetics.mutationKernel = mutationKernelSelect.value as Params['genetics']['mutationKernel']
+})
+bindSlider(sliderMutationStd, labelMutationStd, 'mutationStd', v => params.genetics.mutationStd = v)
+bindSlider(sliderHomEdu, labelHomEdu, 'homEnv', v => params.homophily.education = v)
+bindSlider(sliderHomWealth, labelHomWealth, 'homEnv', v => params.homophily.wealth = v)
+bindSlider(sliderHomParentWealth, labelHomParentWealth, 'homEnv', v => params.homophily.parentWealth = v)
+bindSlider(sliderCapitalReturn, labelCapitalReturn, 'capitalReturn', v => params.capitalReturnRate = v)
+bindSlider(sliderPeriods, labelPeriods, 'periods', v => params.periodsPerGeneration = v)
+bindSlider(sliderReturnVol, labelReturnVol, 'returnVol', v => params.returnVolatility = v)
+bindSlider(sliderInheritance, labelInheritance, 'rate', v => params.inheritanceRate = v)
+bindSlider(sliderCatRate, labelCatRate, 'rate', v => params.catastropheRate = v)
+bindSlider(sliderCatSeverity, labelCatSeverity, 'rate', v => params.catastropheSeverity = v)
+catDistSelect.addEventListener('change', () => {
+  params.catastropheDistribution = catDistSelect.value as Params['catastropheDistribution']
+})
+divisionSelect.addEventListener('change', () => {
+  params.inheritanceDivision = divisionSelect.value as Params['inheritanceDivision']
+})
+bindSlider(sliderInheritDecay, labelInheritDecay, 'inheritDecay', v => params.inheritanceWeightDecay = v)
+bindSlider(sliderPartnership, labelPartnership, 'rate', v => params.partnershipRate = v)
+matingModeSelect.addEventListener('change', () => {
+  params.matingMode = matingModeSelect.value as Params['matingMode']
+})
+bindSlider(sliderSingleFertility, labelSingleFertility, 'relativeFertility', v => params.singleFertility = v)
+fertilityModelSelect.addEventListener('change', () => {
+  params.fertility.model = fertilityModelSelect.value as Params['fertility']['model']
+})
+bindSlider(sliderFertilityMean, labelFertilityMean, 'fertilityMean', v => params.fertility.meanChildren = v)
+bindSlider(sliderFertilityWealth, labelFertilityWealth, 'gradient', v => params.fertility.wealthGradient = v)
+bindSlider(sliderFertilityEdu, labelFertilityEdu, 'gradient', v => params.fertility.educationGradient = v)
+toggleCapPopulation.addEventListener('change', () => {
+  params.fertility.capPopulation = toggleCapPopulation.checked
+})
+bindSlider(sliderWageRate, labelWageRate, 'wageRate', v => params.wageRate = v)
+bindSlider(sliderSavingsBottom, labelSavingsBottom, 'savingsRate', v => params.savingsRateBottom = v)
+bindSlider(sliderSavingsMiddle, labelSavingsMiddle, 'savingsRate', v => params.savingsRateMiddle = v)
+bindSlider(sliderSavingsUpper, labelSavingsUpper, 'savingsRate', v => params.savingsRateUpper = v)
+bindSlider(sliderSavingsTop, labelSavingsTop, 'savingsRate', v => params.savingsRateTop = v)
+bindSlider(sliderCutoffBottom, labelCutoffBottom, 'savingsCutoff', v => params.savingsCutoffBottom = v)
+bindSlider(sliderCutoffMiddle, labelCutoffMiddle, 'savingsCutoff', v => params.savingsCutoffMiddle = v)
+bindSlider(sliderCutoffUpper, labelCutoffUpper, 'savingsCutoff', v => params.savingsCutoffUpper = v)
+toggleAccumulation.addEventListener('change', () => {
+  params.accumulationModel = toggleAccumulation.checked
+})
 
 // feature dropdown
 ;(function initFeatures() {
@@ -0,0 +1,128 @@
+// src/metrics/convergence.test.ts
+import { describe, it, expect } from 'vitest'
+import {
+  meanStandardError,
+  gewekeZ,
+  detectConvergence,
+  detectHistoryConvergence,
+  convergenceStopRule,
+  type StopRuleOptions,
+} from './convergence'
+import { History } from './history'
+import { createRng } from '../rng'
+
+/** AR(1) noise around `level`, with autocorrelation phi and innovation sd */
+function ar1(n: number, phi: number, sd: number, seed: number, level: (t: number) => number = () => 0): number[] {
+  const rng = createRng(seed)
+  let e = 0
+  return Array.from({ length: n }, (_, t) => {
+    e = phi * e + sd * rng.normal()
+    return level(t) + e
+  })
+}
+
+/** The generation a stop rule ends a run on, feeding it the series one generation at a time (-1 = never) */
+function stopGeneration(series: number[], options?: StopRuleOptions): number {
+  const stopWhen = convergenceStopRule(['gini'], options)
+  for (let generation = 1; generation < series.length; generation++) {
+    const history: History = { gini: series.slice(0, generation + 1) }
+    if (stopWhen(history, generation)) return generation
+  }
+  return -1
+}
+
+describe('Convergence Detection - convergence.ts', () => {
+  describe('meanStandardError', () => {
+    it('should be larger for autocorrelated series than for iid ones', () => {
+      const iid = ar1(400, 0, 1, 1)
+      const correlated = ar1(400, 0.8, 0.6, 1)
+      expect(meanStandardError(iid)).toBeCloseTo(1 / 20, 1)
+      expect(meanStandardError(correlated)).toBeGreaterThan(meanStandardError(iid))
+    })
+
+    it('should be zero for a constant series', () => {
+      expect(meanStandardError([0.5, 0.5, 0.5])).toBe(0)
+    })
+  })
+
+  describe('gewekeZ', () => {
+    it('should be small for a stationary series and large for a trend', () => {
+      expect(Math.abs(gewekeZ(ar1(200, 0.5, 0.01, 2)))).toBeLessThan(3)
+      const trend = Array.from({ length: 200 }, (_, t) => 0.3 + 0.002 * t)
+      expect(Math.abs(gewekeZ(trend))).toBeGreaterThan(4)
+    })
+  })
+
+  describe('detectConvergence', () => {
+    it('should find little or no burn-in for a stationary series', () => {
+      const series = ar1(200, 0.5, 0.01, 3, () => 0.6)
+      const result = detectConvergence(series)
+      expect(result.converged).toBe(true)
+      expect(result.burnIn).toBeLessThan(50)
+      expect(result.lo).toBeLessThan(0.6)
+      expect(result.hi).toBeGreaterThan(0.6)
+    })
+
+    it('should skip the transient of a series settling to a level', () => {
+      const series = ar1(200, 0.3, 0.005, 4, t => 0.8 * (1 - Math.exp(-t / 5)))
+      const result = detectConvergence(series)
+      expect(result.converged).toBe(true)
+      expect(result.burnIn).toBeGreaterThan(5)
+      expect(result.mean).toBeCloseTo(0.8, 2)
+      expect(result.lo).toBeLessThan(result.mean)
+      expect(result.hi).toBeGreaterThan(result.mean)
+    })
+
+    it('should not converge on a steady trend', () => {
+      const series = ar1(200, 0.5, 0.005, 5, t => 0.3 + 0.002 * t)
+      const result = detectConvergence(series)
+      expect(result.converged).toBe(false)
+      expect(result.burnIn).toBeNaN()
+      expect(Math.abs(result.z)).toBeGreaterThan(2)
+    })
+
+    it('should need minLength values after burn-in', () => {
+      expect(detectConvergence(Array(15).fill(0.5)).converged).toBe(false)
+      expect(detectConvergence(Array(15).fill(0.5), { minLength: 10 }).converged).toBe(true)
+    })
+
+    it('should skip non-finite values but report original generations', () => {
+      const result = detectConvergence([NaN, ...Array(25).fill(0.5)])
+      expect(result).toMatchObject({ converged: true, burnIn: 1, mean: 0.5, lo: 0.5, hi: 0.5 })
+    })
+  })
+
+  describe('detectHistoryConvergence', () => {
+    it('should test each requested metric', () => {
+      const history = {
+        gini: ar1(100, 0.5, 0.01, 6, () => 0.4),
+        meanWealth: Array.from({ length: 100 }, (_, t) => 1 + 0.05 * t),
+      }
+      const results = detectHistoryConvergence(history, ['gini', 'meanWealth', 'ige'])
+      expect(results.gini.converged).toBe(true)
+      expect(results.meanWealth.converged).toBe(false)
+      expect(results.ige.converged).toBe(false)
+    })
+  })
+
+  describe('convergenceStopRule', () => {
+    it('should not stop a run that is still trending', () => {
+      const trend = ar1(300, 0.5, 0.01, 7, t => 0.3 + 0.002 * t)
+      expect(stopGeneration(trend)).toBe(-1)
+      // stopping on the first pass of a check every generation would end it early
+      expect(stopGeneration(trend, { every: 1, minGenerations: 0, confirm: 1 })).toBeGreaterThan(0)
+    })
+
+    it('should stop a stationary run once the test has kept passing', () => {
+      const generation = stopGeneration(ar1(300, 0.5, 0.01, 8, () => 0.6))
+      expect(generation).toBeGreaterThanOrEqual(75)
+      expect(generation).toBeLessThan(300)
+      expect(generation % 5).toBe(0)
+    })
+
+    it('should not stop before minGenerations', () => {
+      const constant = Array(200).fill(0.5)
+      expect(stopGeneration(constant, { minGenerations: 100, confirm: 1 })).toBe(100)
+    })
+  })
+})
@@ -0,0 +1,141 @@
+// src/metrics/convergence.ts
+// Steady-state detection for per-generation metric series (Geweke-style).
+import { mean, variance } from './stats'
+import { History, historyLength } from './history'
+
+/** Lag-1 autocorrelation, clipped to [0, 0.99] */
+function lag1Autocorrelation(xs: number[]): number {
+  const n = xs.length
+  if (n < 3) return 0
+  const m = mean(xs)
+  const v = variance(xs)
+  if (v === 0) return 0
+  let lag = 0
+  for (let i = 1; i < n; i++) lag += (xs[i] - m) * (xs[i - 1] - m)
+  return Math.min(0.99, Math.max(0, lag / ((n - 1) * v)))
+}
+
+/**
+ * Standard error of the mean of an autocorrelated series, inflating the iid
+ * variance by (1 + ρ) / (1 − ρ) for the lag-1 autocorrelation ρ (AR(1)
+ * approximation to the spectral density at zero). Negative ρ is treated as 0.
+ */
+export function meanStandardError(xs: number[], rho = lag1Autocorrelation(xs)): number {
+  const n = xs.length
+  if (n < 2) return NaN
+  return Math.sqrt((variance(xs) / n) * (1 + rho) / (1 - rho))
+}
+
+/**
+ * Geweke z-score: difference between the means of the first `first` and the
+ * last `last` fractions of the series (at least 5 and 2 values), over its
+ * standard error. |z| ≲ 2 is consistent with a stationary series. The
+ * autocorrelation comes from the last part alone, since a transient in the
+ * first part would pass for strong autocorrelation and mask itself.
+ */
+export function gewekeZ(xs: number[], first = 0.1, last = 0.5): number {
+  const a = xs.slice(0, Math.max(5, Math.floor(first * xs.length)))
+  const b = xs.slice(xs.length - Math.max(2, Math.floor(last * xs.length)))
+  const rho = lag1Autocorrelation(b)
+  const diff = mean(a) - mean(b)
+  const se = Math.sqrt(meanStandardError(a, rho) ** 2 + meanStandardError(b, rho) ** 2)
+  if (se === 0) return diff === 0 ? 0 : Math.sign(diff) * Infinity
+  return diff / se
+}
+
+export interface ConvergenceOptions {
+  /** |Geweke z| below this counts as stationary (default 2) */
+  threshold?: number
+  /** Fewest generations after burn-in before declaring convergence (default 20) */
+  minLength?: number
+}
+
+export interface ConvergenceResult {
+  converged: boolean
+  /** First generation of the stationary part (NaN if not converged) */
+  burnIn: number
+  /** Steady-state mean after burn-in, with a 95% band (NaN if not converged) */
+  mean: number
+  lo: number
+  hi: number
+  /** Geweke z of the stationary part, or of the whole series if not converged */
+  z: number
+}
+
+/**
+ * Find the burn-in of a series: the earliest generation from which the rest of
+ * the series passes the Geweke test, and the steady-state mean from there on.
+ * As in the usual truncation practice, at most the first half is discarded and
+ * the rest must hold at least minLength values. Non-finite values (e.g. the
+ * founders' IGE) are skipped; indices refer to the original series.
+ */
+export function detectConvergence(series: number[], options: ConvergenceOptions = {}): ConvergenceResult {
+  const threshold = options.threshold ?? 2
+  const minLength = Math.max(4, options.minLength ?? 20)
+  const indices = series.map((_, i) => i).filter(i => Number.isFinite(series[i]))
+  const values = indices.map(i => series[i])
+
+  for (let start = 0; start <= values.length / 2 && values.length - start >= minLength; start++) {
+    const tail = values.slice(start)
+    const z = gewekeZ(tail)
+    if (Math.abs(z) < threshold) {
+      const m = mean(tail)
+      const half = 1.96 * meanStandardError(tail)
+      return { converged: true, burnIn: indices[start], mean: m, lo: m - half, hi: m + half, z }
+    }
+  }
+  return {
+    converged: false, burnIn: NaN, mean: NaN, lo: NaN, hi: NaN,
+    z: values.length >= 4 ? gewekeZ(values) : NaN,
+  }
+}
+
+/** Convergence of several metrics of a history; the run has converged once all have */
+export function detectHistoryConvergence(
+  history: History,
+  metrics: string[],
+  options: ConvergenceOptions = {}
+): Record<string, ConvergenceResult> {
+  return Object.fromEntries(metrics.map(m => [m, detectConvergence(history[m] ?? [], options)]))
+}
+
+export interface StopRuleOptions extends ConvergenceOptions {
+  /** Generations between checks (default 5) */
+  every?: number
+  /** No stop before this generation (default 50) */
+  minGenerations?: number
+  /**
+   * After the first passing check, every later check must pass too until the
+   * history is this many times as long (default 1.5); a failure starts over
+   */
+  confirm?: number
+}
+
+/**
+ * A stopWhen for runSimulation that ends the run once all `metrics` have
+ * converged. Stopping on the first pass of a test repeated every generation
+ * would often stop a slow trend early, since a short series can't tell a drift
+ * from noise; checking every few generations after a minimum run, and asking
+ * the test to keep passing while the series grows, rules most of these out.
+ */
+export function convergenceStopRule(
+  metrics: string[],
+  options: StopRuleOptions = {}
+): (history: History, generation: number) => boolean {
+  const every = Math.max(1, options.every ?? 5)
+  const minGenerations = options.minGenerations ?? 50
+  const confirm = options.confirm ?? 1.5
+  let firstPass = -1
+
+  return (history, generation) => {
+    if (generation < minGenerations || generation % every !== 0) return false
+    const results = detectHistoryConvergence(history, metrics, options)
+    if (!Object.values(results).every(r => r.converged)) {
+      firstPass = -1
+      return false
+    }
+    const length = historyLength(history)
+    if (firstPass < 0) firstPass = length
+    return length >= confirm * firstPass
+  }
+}
@@ -0,0 +1,45 @@
+// src/metrics/history.test.ts
+import { describe, it, expect } from 'vitest'
+import { History, appendRecord, historyLength, clearHistory } from './history'
+
+describe('Metric History - history.ts', () => {
+  describe('appendRecord', () => {
+    it('should append one entry per metric', () => {
+      const history: History = {}
+      appendRecord(history, { gini: 0.3, top10: 0.4 })
+      appendRecord(history, { gini: 0.35, top10: 0.45 })
+
+      expect(history).toEqual({ gini: [0.3, 0.35], top10: [0.4, 0.45] })
+      expect(historyLength(history)).toBe(2)
+    })
+
+    it('should back-fill a metric first seen later with NaN', () => {
+      const history: History = {}
+      appendRecord(history, { gini: 0.3 })
+      appendRecord(history, { gini: 0.35, ige: 0.5 })
+
+      expect(history.ige).toHaveLength(2)
+      expect(history.ige[0]).toBeNaN()
+      expect(history.ige[1]).toBe(0.5)
+    })
+
+    it('should pad metrics missing from a record with NaN', () => {
+      const history: History = {}
+      appendRecord(history, { gini: 0.3, ige: 0.5 })
+      appendRecord(history, { gini: 0.35 })
+
+      expect(history.ige[1]).toBeNaN()
+      expect(history.gini).toEqual([0.3, 0.35])
+    })
+  })
+
+  describe('clearHistory', () => {
+    it('should remove every series', () => {
+      const history: History = { gini: [0.3] }
+      clearHistory(history)
+
+      expect(history).toEqual({})
+      expect(historyLength(history)).toBe(0)
+    })
+  })
+})
@@ -0,0 +1,31 @@
+// src/metrics/history.ts
+// Per-step metric histories keyed by metric name, so charts and exports can
+// pick any metric without a dedicated array per measure.
+
+/** Named series with one entry per recorded step (generation or year) */
+export type History = Record<string, number[]>
+
+/** Number of steps recorded so far */
+export function historyLength(history: History): number {
+  return Math.max(0, ...Object.values(history).map(s => s.length))
+}
+
+/**
+ * Append one step. Metrics missing from `record` get NaN, and a metric seen for
+ * the first time is back-filled with NaN, so every series stays aligned with the
+ * step index (e.g. mobility measures that only exist from generation 1 on).
+ */
+export function appendRecord(history: History, record: Record<string, number>): void {
+  const steps = historyLength(history)
+  for (const key of Object.keys(record)) {
+    if (!(key in history)) history[key] = Array(steps).fill(NaN)
+  }
+  for (const key of Object.keys(history)) {
+    history[key].push(key in record ? record[key] : NaN)
+  }
+}
+
+/** Empty every series in place */
+export function clearHistory(history: History): void {
+  for (const key of Object.keys(history)) delete history[key]
+}
@@ -0,0 +1,212 @@
+// src/metrics/inequality.test.ts
+import { describe, it, expect } from 'vitest'
+import {
+  computeGini,
+  computeWeightedGini,
+  topShare,
+  bottomShare,
+  theilT,
+  theilL,
+  atkinson,
+  palma,
+  quantile,
+  p90p10,
+  hillTailIndex,
+  computeInequality
+} from './inequality'
+import { createRng } from '../rng'
+import { computeLorenz } from '../viz/plots'
+import type { Agent } from '../model'
+
+/** The previous implementation: trapezoid rule on the Lorenz curve */
+function trapezoidGini(wealth: number[]): number {
+  const lorenz = computeLorenz(wealth.map(w => ({ wealth: w } as Agent)))
+  let area = 0
+  for (let i = 1; i < lorenz.length; i++) {
+    area += ((lorenz[i - 1][1] + lorenz[i][1]) / 2) * (lorenz[i][0] - lorenz[i - 1][0])
+  }
+  return 1 - 2 * area
+}
+
+/** Random wealth arrays of random length, from a few differently shaped distributions */
+function randomWealthArrays(seed: number, count: number, allowDebt = false): number[][] {
+  const rng = createRng(seed)
+  return Array.from({ length: count }, () => {
+    const n = 1 + rng.int(200)
+    const shape = rng.int(3)
+    return Array.from({ length: n }, () => {
+      const x = shape === 0 ? rng.random() * 1000
+        : shape === 1 ? Math.exp(rng.normal(10, 2))
+        : Math.floor(rng.random() * 5)    // many ties and zeros
+      return allowDebt && rng.random() < 0.3 ? -x : x
+    })
+  })
+}
+
+const equal = Array(100).fill(1000)
+// one agent holds everything
+const concentrated = [...Array(99).fill(0), 1000]
+
+describe('Inequality Metrics - inequality.ts', () => {
+  describe('computeGini', () => {
+    it('should match the trapezoid Lorenz implementation on positive data', () => {
+      randomWealthArrays(1, 300)
+        .filter(wealth => wealth.some(w => w > 0))
+        .forEach(wealth => {
+          expect(computeGini(wealth)).toBeCloseTo(trapezoidGini(wealth), 10)
+        })
+    })
+
+    it('should match the mean absolute difference definition', () => {
+      randomWealthArrays(2, 50, true).forEach(wealth => {
+        let pairs = 0
+        wealth.forEach(x => wealth.forEach(y => { pairs += Math.abs(x - y) }))
+        const absTotal = wealth.reduce((s, x) => s + Math.abs(x), 0)
+        const expected = absTotal === 0 ? 0 : pairs / (2 * wealth.length * absTotal)
+        expect(computeGini(wealth)).toBeCloseTo(expected, 10)
+      })
+    })
+
+    it('should stay within [0, 1] with debts', () => {
+      randomWealthArrays(3, 300, true).forEach(wealth => {
+        const g = computeGini(wealth)
+        expect(g).toBeGreaterThanOrEqual(0)
+        expect(g).toBeLessThanOrEqual(1 + 1e-12)
+      })
+    })
+
+    it('should match hand-computed values with debts', () => {
+      // Σ|xᵢ − xⱼ| = 4, n = 2, Σ|x| = 2
+      expect(computeGini([-1, 1])).toBeCloseTo(0.5)
+      expect(computeGini([-5, -5, -5])).toBeCloseTo(0)
+      expect(computeGini([-10, 0, 0, 10])).toBeCloseTo(0.5)
+    })
+
+    it('should return 0 for empty and all-zero wealth', () => {
+      expect(computeGini([])).toBe(0)
+      expect(computeGini([0, 0, 0])).toBe(0)
+    })
+  })
+
+  describe('computeWeightedGini', () => {
+    it('should equal the unweighted Gini with unit weights', () => {
+      randomWealthArrays(4, 50, true).forEach(wealth => {
+        expect(computeWeightedGini(wealth, wealth.map(() => 1))).toBeCloseTo(computeGini(wealth), 12)
+      })
+    })
+
+    it('should equal the Gini of the replicated data with integer weights', () => {
+      const rng = createRng(5)
+      randomWealthArrays(6, 50, true).forEach(wealth => {
+        const weights = wealth.map(() => 1 + rng.int(4))
+        const replicated = wealth.flatMap((w, i) => Array(weights[i]).fill(w))
+        expect(computeWeightedGini(wealth, weights)).toBeCloseTo(computeGini(replicated), 10)
+      })
+    })
+
+    it('should ignore values with zero weight', () => {
+      expect(computeWeightedGini([1, 2, 1000], [1, 1, 0])).toBeCloseTo(computeGini([1, 2]))
+    })
+  })
+
+  describe('wealth shares', () => {
+    it('should give shares equal to population shares under equality', () => {
+      expect(topShare(equal, 0.01)).toBeCloseTo(0.01)
+      expect(topShare(equal, 0.1)).toBeCloseTo(0.1)
+      expect(bottomShare(equal, 0.5)).toBeCloseTo(0.5)
+    })
+
+    it('should give the top everything under full concentration', () => {
+      expect(topShare(concentrated, 0.01)).toBe(1)
+      expect(bottomShare(concentrated, 0.5)).toBe(0)
+    })
+
+    it('should compute shares of a small known distribution', () => {
+      const wealth = [1, 2, 3, 4, 10]
+      expect(topShare(wealth, 0.2)).toBeCloseTo(10 / 20)
+      expect(bottomShare(wealth, 0.4)).toBeCloseTo(3 / 20)
+    })
+  })
+
+  describe('Theil indices', () => {
+    it('should be zero under equality', () => {
+      expect(theilT(equal)).toBeCloseTo(0)
+      expect(theilL(equal)).toBeCloseTo(0)
+    })
+
+    it('should match hand-computed values', () => {
+      // μ = 2.5; T = (0.4 ln 0.4 + 1.6 ln 1.6) / 2; L = ln(μ / geometric mean) = ln 1.25
+      expect(theilT([1, 4])).toBeCloseTo((0.4 * Math.log(0.4) + 1.6 * Math.log(1.6)) / 2)
+      expect(theilL([1, 4])).toBeCloseTo(Math.log(1.25))
+    })
+
+    it('should reach ln n when one of n agents holds almost everything', () => {
+      const n = 100
+      const wealth = [...Array(n - 1).fill(1e-12), 1]
+      expect(theilT(wealth)).toBeCloseTo(Math.log(n), 3)
+    })
+  })
+
+  describe('atkinson', () => {
+    it('should be zero under equality for any aversion', () => {
+      [0.5, 1, 2].forEach(e => expect(atkinson(equal, e)).toBeCloseTo(0))
+    })
+
+    it('should match hand-computed values', () => {
+      // mean 2.5; geometric mean 2; harmonic mean 1.6
+      expect(atkinson([1, 4], 1)).toBeCloseTo(1 - 2 / 2.5)
+      expect(atkinson([1, 4], 2)).toBeCloseTo(1 - 1.6 / 2.5)
+      expect(atkinson([1, 4], 0)).toBeCloseTo(0)
+    })
+
+    it('should increase with inequality aversion', () => {
+      const wealth = [1, 2, 5, 10, 100]
+      expect(atkinson(wealth, 0.5)).toBeLessThan(atkinson(wealth, 1))
+      expect(atkinson(wealth, 1)).toBeLessThan(atkinson(wealth, 2))
+    })
+  })
+
+  describe('palma and percentile ratios', () => {
+    it('should give a Palma ratio of 1/4 under equality', () => {
+      expect(palma(equal)).toBeCloseTo(0.25)
+    })
+
+    it('should interpolate quantiles', () => {
+      expect(quantile([1, 2, 3, 4, 5], 0.5)).toBe(3)
+      expect(quantile([0, 10], 0.25)).toBeCloseTo(2.5)
+    })
+
+    it('should compute P90/P10', () => {
+      const wealth = Array.from({ length: 11 }, (_, i) => 10 * (i + 1))
+      // P10 = 20, P90 = 100
+      expect(p90p10(wealth)).toBeCloseTo(5)
+      expect(p90p10(equal)).toBe(1)
+    })
+  })
+
+  describe('hillTailIndex', () => {
+    it('should recover the exponent of a Pareto sample', () => {
+      const rng = createRng(1)
+      const alpha = 1.5
+      // inverse CDF of Pareto(x_m = 1, α)
+      const wealth = Array.from({ length: 20000 }, () => Math.pow(1 - rng.random(), -1 / alpha))
+      expect(Math.abs(hillTailIndex(wealth, 0.05) - alpha)).toBeLessThan(0.15)
+    })
+
+    it('should return NaN without a tail', () => {
+      expect(hillTailIndex([1, 2, 3])).toBeNaN()
+      expect(hillTailIndex(equal)).toBeNaN()
+    })
+  })
+
+  describe('computeInequality', () => {
+    it('should bundle every index', () => {
+      const stats = computeInequality(equal)
+      expect(stats.top10).toBeCloseTo(0.1)
+      expect(stats.theilT).toBeCloseTo(0)
+      expect(stats.atkinson1).toBeCloseTo(0)
+      expect(stats.palma).toBeCloseTo(0.25)
+      expect(stats.p90p10).toBe(1)
+    })
+  })
+})
@@ -0,0 +1,161 @@
+// src/metrics/inequality.ts
+// Inequality indices, all as pure functions of a wealth array.
+// Log-based indices (Theil, Atkinson, Hill) are only defined for positive wealth,
+// so they ignore non-positive values.
+
+const positive = (wealth: number[]) => wealth.filter(w => w > 0)
+const sum = (xs: number[]) => xs.reduce((s, x) => s + x, 0)
+
+/**
+ * Exact Gini coefficient from the sorted-rank formula, O(n log n):
+ *
+ *   G = Σᵢ (2i − n − 1) x₍ᵢ₎ / (n Σ |x|),   x₍₁₎ ≤ … ≤ x₍ₙ₎
+ *
+ * With non-negative wealth this is the usual mean-difference Gini (and equals the
+ * trapezoid rule on the Lorenz curve). With debts, the denominator uses the total
+ * absolute wealth instead of the net total — the normalization of Raffinetti,
+ * Siletti & Vernizzi (2015) — so G stays in [0, 1]. All-zero wealth gives 0.
+ */
+export function computeGini(wealth: number[]): number {
+  return computeWeightedGini(wealth, wealth.map(() => 1))
+}
+
+/**
+ * Gini coefficient with non-negative frequency weights (e.g. household sizes),
+ * normalized as in computeGini:
+ *
+ *   G = Σᵢ wᵢ x₍ᵢ₎ (2 Cᵢ₋₁ + wᵢ − W) / (W Σ wᵢ |xᵢ|)
+ *
+ * where Cᵢ is the cumulative weight of the i poorest and W the total weight.
+ * Integer weights give the same result as repeating each value.
+ */
+export function computeWeightedGini(wealth: number[], weights: number[]): number {
+  const n = wealth.length
+  if (n === 0) return 0
+  const order = wealth.map((_, i) => i).sort((i, j) => wealth[i] - wealth[j])
+  const W = sum(weights)
+
+  let cumulative = 0
+  let numerator = 0
+  let absTotal = 0
+  for (const i of order) {
+    const w = weights[i]
+    numerator += w * wealth[i] * (2 * cumulative + w - W)
+    absTotal += w * Math.abs(wealth[i])
+    cumulative += w
+  }
+  return absTotal === 0 || W === 0 ? 0 : numerator / (W * absTotal)
+}
+
+/** Share of total wealth held by the richest fraction p of the population */
+export function topShare(wealth: number[], p: number): number {
+  const total = sum(wealth)
+  if (wealth.length === 0 || total === 0) return 0
+  const k = Math.min(wealth.length, Math.max(1, Math.round(p * wealth.length)))
+  const sorted = [...wealth].sort((a, b) => b - a)
+  return sum(sorted.slice(0, k)) / total
+}
+
+/** Share of total wealth held by the poorest fraction p of the population */
+export function bottomShare(wealth: number[], p: number): number {
+  const total = sum(wealth)
+  if (wealth.length === 0 || total === 0) return 0
+  const k = Math.min(wealth.length, Math.max(1, Math.round(p * wealth.length)))
+  const sorted = [...wealth].sort((a, b) => a - b)
+  return sum(sorted.slice(0, k)) / total
+}
+
+/** Theil T index (GE(1)): mean of (x/μ) ln(x/μ); 0 = equality, ln n = one holder */
+export function theilT(wealth: number[]): number {
+  const xs = positive(wealth)
+  if (xs.length === 0) return 0
+  const mu = sum(xs) / xs.length
+  return sum(xs.map(x => (x / mu) * Math.log(x / mu))) / xs.length
+}
+
+/** Theil L index (GE(0), mean log deviation): mean of ln(μ/x) */
+export function theilL(wealth: number[]): number {
+  const xs = positive(wealth)
+  if (xs.length === 0) return 0
+  const mu = sum(xs) / xs.length
+  return sum(xs.map(x => Math.log(mu / x))) / xs.length
+}
+
+/**
+ * Atkinson index with inequality aversion ε ≥ 0: one minus the ratio of the
+ * equally distributed equivalent (a power mean of order 1 − ε) to the mean
+ */
+export function atkinson(wealth: number[], epsilon: number): number {
+  const xs = positive(wealth)
+  if (xs.length === 0) return 0
+  const mu = sum(xs) / xs.length
+  const ede = epsilon === 1
+    ? Math.exp(sum(xs.map(Math.log)) / xs.length)
+    : Math.pow(sum(xs.map(x => Math.pow(x, 1 - epsilon))) / xs.length, 1 / (1 - epsilon))
+  return 1 - ede / mu
+}
+
+/** Palma ratio: share of the top 10% over share of the bottom 40% */
+export function palma(wealth: number[]): number {
+  return topShare(wealth, 0.1) / bottomShare(wealth, 0.4)
+}
+
+/** Quantile with linear interpolation between order statistics */
+export function quantile(values: number[], q: number): number {
+  if (values.length === 0) return NaN
+  const sorted = [...values].sort((a, b) => a - b)
+  const h = (sorted.length - 1) * q
+  const lo = Math.floor(h)
+  const hi = Math.ceil(h)
+  return sorted[lo] + (h - lo) * (sorted[hi] - sorted[lo])
+}
+
+/** Ratio of the 90th to the 10th wealth percentile */
+export function p90p10(wealth: number[]): number {
+  return quantile(wealth, 0.9) / quantile(wealth, 0.1)
+}
+
+/**
+ * Hill estimate of the Pareto tail exponent α from the richest tailFraction
+ * of positive wealth: k / Σ ln(X(i) / X(k+1)). Smaller α ⇒ fatter tail.
+ */
+export function hillTailIndex(wealth: number[], tailFraction = 0.05): number {
+  const sorted = positive(wealth).sort((a, b) => b - a)
+  const k = Math.min(sorted.length - 1, Math.floor(tailFraction * sorted.length))
+  if (k < 1) return NaN
+  const threshold = sorted[k]
+  let s = 0
+  for (let i = 0; i < k; i++) s += Math.log(sorted[i] / threshold)
+  return s === 0 ? NaN : k / s
+}
+
+/** The whole suite for one wealth distribution */
+export interface InequalityStats {
+  top1: number
+  top10: number
+  bottom50: number
+  theilT: number
+  theilL: number
+  atkinson05: number
+  atkinson1: number
+  atkinson2: number
+  palma: number
+  p90p10: number
+  hill: number
+}
+
+export function computeInequality(wealth: number[]): InequalityStats {
+  return {
+    top1:       topShare(wealth, 0.01),
+    top10:      topShare(wealth, 0.1),
+    bottom50:   bottomShare(wealth, 0.5),
+    theilT:     theilT(wealth),
+    theilL:     theilL(wealth),
+    atkinson05: atkinson(wealth, 0.5),
+    atkinson1:  atkinson(wealth, 1),
+    atkinson2:  atkinson(wealth, 2),
+    palma:      palma(wealth),
+    p90p10:     p90p10(wealth),
+    hill:       hillTailIndex(wealth),
+  }
+}
@@ -0,0 +1,114 @@
+// src/metrics/mobility.test.ts
+import { describe, it, expect } from 'vitest'
+import {
+  percentileRanks,
+  computeIGE,
+  computeRankRankSlope,
+  computeTransitionMatrix,
+  computeMobility
+} from './mobility'
+import { createRng } from '../rng'
+import type { Agent } from '../model'
+
+const founder: Agent = {
+  id: -1, alleles: [0, 0], meanAllele: 0, env: 0, rawenv: 0,
+  educationScore: 0, wealth: 0, parentWealth: 0, parents: null
+}
+
+/** A child of `parentWealth` parents who ends up with `wealth` */
+const makeChild = (id: number, parentWealth: number, wealth: number): Agent => ({
+  ...founder, id, parentWealth, wealth, parents: [founder, founder]
+})
+
+describe('Mobility Metrics - mobility.ts', () => {
+  describe('percentileRanks', () => {
+    it('should give mid-ranks in (0, 1)', () => {
+      expect(percentileRanks([30, 10, 20, 40])).toEqual([0.625, 0.125, 0.375, 0.875])
+    })
+
+    it('should share ranks between ties', () => {
+      expect(percentileRanks([5, 5, 1, 9])).toEqual([0.5, 0.5, 0.125, 0.875])
+    })
+  })
+
+  describe('computeIGE', () => {
+    it('should recover a known elasticity', () => {
+      const rng = createRng(1)
+      // log w = 2 + 0.4 log pw exactly
+      const pop = Array.from({ length: 200 }, (_, i) => {
+        const pw = Math.exp(rng.normal(11, 1))
+        return makeChild(i, pw, Math.exp(2) * Math.pow(pw, 0.4))
+      })
+      expect(computeIGE(pop)).toBeCloseTo(0.4)
+    })
+
+    it('should drop non-positive wealth and founders', () => {
+      const pop = [
+        makeChild(0, 100, 100),
+        makeChild(1, 1000, 1000),
+        makeChild(2, 10000, 0),      // no log
+        makeChild(3, -50, 10),       // no log
+        { ...founder, id: 4, wealth: 1, parentWealth: 1e9 }
+      ]
+      expect(computeIGE(pop)).toBeCloseTo(1)
+    })
+  })
+
+  describe('computeRankRankSlope', () => {
+    it('should be one when children keep their parents\' rank', () => {
+      const pop = Array.from({ length: 50 }, (_, i) => makeChild(i, 1000 * (i + 1), 10 * (i + 1) ** 2))
+      expect(computeRankRankSlope(pop)).toBeCloseTo(1)
+    })
+
+    it('should be minus one when ranks are reversed', () => {
+      const pop = Array.from({ length: 50 }, (_, i) => makeChild(i, 1000 * (i + 1), 1000 * (50 - i)))
+      expect(computeRankRankSlope(pop)).toBeCloseTo(-1)
+    })
+
+    it('should be near zero when child wealth is independent of parents\'', () => {
+      const rng = createRng(2)
+      const pop = Array.from({ length: 5000 }, (_, i) => makeChild(i, rng.random(), rng.random()))
+      expect(Math.abs(computeRankRankSlope(pop))).toBeLessThan(0.05)
+    })
+  })
+
+  describe('computeTransitionMatrix', () => {
+    it('should be the identity under perfect persistence', () => {
+      const pop = Array.from({ length: 100 }, (_, i) => makeChild(i, i + 1, 2 * (i + 1)))
+      const m = computeTransitionMatrix(pop)
+
+      expect(m).toHaveLength(5)
+      m.forEach((row, i) => row.forEach((p, j) => expect(p).toBe(i === j ? 1 : 0)))
+    })
+
+    it('should be anti-diagonal when ranks are reversed', () => {
+      const pop = Array.from({ length: 100 }, (_, i) => makeChild(i, i + 1, 100 - i))
+      const m = computeTransitionMatrix(pop, 4)
+
+      expect(m).toHaveLength(4)
+      m.forEach((row, i) => row.forEach((p, j) => expect(p).toBe(i + j === 3 ? 1 : 0)))
+    })
+
+    it('should have rows summing to one and near-uniform entries without persistence', () => {
+      const rng = createRng(3)
+      const pop = Array.from({ length: 10000 }, (_, i) => makeChild(i, rng.random(), rng.random()))
+      const m = computeTransitionMatrix(pop)
+
+      m.forEach(row => {
+        expect(row.reduce((s, p) => s + p, 0)).toBeCloseTo(1)
+        row.forEach(p => expect(Math.abs(p - 0.2)).toBeLessThan(0.04))
+      })
+    })
+  })
+
+  describe('computeMobility', () => {
+    it('should bundle all three measures', () => {
+      const pop = Array.from({ length: 100 }, (_, i) => makeChild(i, i + 1, i + 1))
+      const stats = computeMobility(pop)
+
+      expect(stats.ige).toBeCloseTo(1)
+      expect(stats.rankRankSlope).toBeCloseTo(1)
+      expect(stats.transitionMatrix[0][0]).toBe(1)
+    })
+  })
+})
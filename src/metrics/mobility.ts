@@ -0,0 +1,81 @@
+// src/metrics/mobility.ts
+// Intergenerational mobility: how closely children's wealth follows their parents'.
+// Parents are the `parentWealth` household; founders (no parents) are left out.
+import type { Agent } from '../model'
+import { slope } from './stats'
+
+const withParents = (pop: Agent[]) => pop.filter(a => a.parents !== null)
+
+/**
+ * Mid-ranks scaled to (0, 1): the i-th smallest of n values gets (i + 0.5) / n,
+ * and tied values share the average of their ranks.
+ */
+export function percentileRanks(values: number[]): number[] {
+  const n = values.length
+  const order = values.map((_, i) => i).sort((i, j) => values[i] - values[j])
+  const ranks = new Array<number>(n)
+  let start = 0
+  while (start < n) {
+    let end = start
+    while (end + 1 < n && values[order[end + 1]] === values[order[start]]) end++
+    const midRank = ((start + end) / 2 + 0.5) / n
+    for (let k = start; k <= end; k++) ranks[order[k]] = midRank
+    start = end + 1
+  }
+  return ranks
+}
+
+/**
+ * Intergenerational elasticity: slope of log child wealth on log parental wealth.
+ * Households with non-positive wealth on either side have no log and are dropped.
+ */
+export function computeIGE(pop: Agent[]): number {
+  const kids = withParents(pop).filter(a => a.wealth > 0 && a.parentWealth > 0)
+  return slope(
+    kids.map(a => Math.log(a.parentWealth)),
+    kids.map(a => Math.log(a.wealth))
+  )
+}
+
+/** Chetty-style rank-rank slope: child wealth percentile on parental wealth percentile */
+export function computeRankRankSlope(pop: Agent[]): number {
+  const kids = withParents(pop)
+  return slope(
+    percentileRanks(kids.map(a => a.parentWealth)),
+    percentileRanks(kids.map(a => a.wealth))
+  )
+}
+
+/**
+ * Quantile transition matrix: entry [i][j] is the share of children born into
+ * parental quantile i who end up in wealth quantile j (rows sum to 1).
+ * Quintiles by default.
+ */
+export function computeTransitionMatrix(pop: Agent[], nQuantiles = 5): number[][] {
+  const kids = withParents(pop)
+  const quantile = (p: number) => Math.min(nQuantiles - 1, Math.floor(p * nQuantiles))
+  const parentQ = percentileRanks(kids.map(a => a.parentWealth)).map(quantile)
+  const childQ = percentileRanks(kids.map(a => a.wealth)).map(quantile)
+
+  const counts = Array.from({ length: nQuantiles }, () => new Array<number>(nQuantiles).fill(0))
+  kids.forEach((_, i) => { counts[parentQ[i]][childQ[i]]++ })
+  return counts.map(row => {
+    const total = row.reduce((s, c) => s + c, 0)
+    return row.map(c => total === 0 ? 0 : c / total)
+  })
+}
+
+/** Headline mobility statistics for one generation */
+export interface MobilityStats {
+  ige: number
+  rankRankSlope: number
+  transitionMatrix: number[][]
+}
+
+export function computeMobility(pop: Agent[], nQuantiles = 5): MobilityStats {
+  return {
+    ige:              computeIGE(pop),
+    rankRankSlope:    computeRankRankSlope(pop),
+    transitionMatrix: computeTransitionMatrix(pop, nQuantiles),
+  }
+}
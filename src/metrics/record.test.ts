@@ -0,0 +1,79 @@
+// src/metrics/record.test.ts
+import { describe, it, expect } from 'vitest'
+import { generationRecord, wealthRecord } from './record'
+import { computeGini } from './inequality'
+import { computeGenerationDiagnostics } from './variance'
+import { createRng } from '../rng'
+import { defaultParams, initializePopulation, nextGeneration, mutationCount, type Params } from '../model'
+
+const params: Params = { ...defaultParams, populationSize: 200 }
+
+describe('Generation Records - record.ts', () => {
+  describe('wealthRecord', () => {
+    it('should include the Gini and the inequality suite', () => {
+      const wealth = [1, 2, 3, 4, 10]
+      const record = wealthRecord(wealth)
+      expect(record.gini).toBeCloseTo(computeGini(wealth))
+      expect(record.top10).toBeCloseTo(10 / 20)
+    })
+  })
+
+  describe('generationRecord', () => {
+    it('should leave parent–child measures out for founders', () => {
+      const record = generationRecord(initializePopulation(params, createRng(1)))
+      expect(record.meanEducation).toBeDefined()
+      expect(record).not.toHaveProperty('ige')
+      expect(record).not.toHaveProperty('partnered')
+    })
+
+    it('should include parent–child measures for children', () => {
+      const rng = createRng(2)
+      const children = nextGeneration(initializePopulation(params, rng), params, rng)
+      const record = generationRecord(children)
+      expect(Number.isFinite(record.ige)).toBe(true)
+      expect(Number.isFinite(record.rankRankSlope)).toBe(true)
+      expect(Number.isFinite(record.geneShare)).toBe(true)
+    })
+
+    it('should record the mutation count', () => {
+      const mutating: Params = { ...params, genetics: { ...params.genetics, mutationRate: 0.5 } }
+      const rng = createRng(5)
+      const children = nextGeneration(initializePopulation(mutating, rng), mutating, rng)
+      const record = generationRecord(children)
+
+      expect(record.mutations).toBe(mutationCount(children))
+      expect(record.mutations).toBeGreaterThan(0)
+      expect(generationRecord(initializePopulation(params, createRng(6))).mutations).toBe(0)
+    })
+
+    it('should record the full variance decompositions and parent–offspring slopes', () => {
+      const rng = createRng(3)
+      const children = nextGeneration(initializePopulation(params, rng), params, rng)
+      const record = generationRecord(children)
+      const { education, wealth, parentOffspring } = computeGenerationDiagnostics(children)
+
+      expect(record).toMatchObject({
+        geneShare:               education.gene,
+        eduEnvShare:             education.env,
+        eduParentWealthShare:    education.parentWealth,
+        eduNoiseShare:           education.noise,
+        wealthGeneShare:         wealth.gene,
+        wealthEnvShare:          wealth.env,
+        wealthParentWealthShare: wealth.parentWealth,
+        wealthNoiseShare:        wealth.noise,
+        poSlope:                 parentOffspring.education,
+        poSlopeGene:             parentOffspring.gene,
+        poSlopeEnv:              parentOffspring.env,
+        poSlopeWealth:           parentOffspring.wealth,
+      })
+      expect(record.geneShare + record.eduEnvShare + record.eduParentWealthShare + record.eduNoiseShare).toBeCloseTo(1)
+      expect(record.wealthGeneShare + record.wealthEnvShare + record.wealthParentWealthShare + record.wealthNoiseShare).toBeCloseTo(1)
+    })
+
+    it('should leave the decompositions out for founders', () => {
+      const record = generationRecord(initializePopulation(params, createRng(4)))
+      expect(record).not.toHaveProperty('eduNoiseShare')
+      expect(record).not.toHaveProperty('poSlopeWealth')
+    })
+  })
+})
@@ -0,0 +1,50 @@
+// src/metrics/record.ts
+// The per-generation metric record shared by the browser charts and the CLI.
+import { Agent, PairingStats, catastropheFraction, mutationCount } from '../model'
+import { mean, variance } from './stats'
+import { computeGini, computeInequality } from './inequality'
+import { computeGenerationDiagnostics } from './variance'
+import { computeIGE, computeRankRankSlope } from './mobility'
+
+/** Wealth-distribution measures, recorded per generation and per year */
+export function wealthRecord(wealth: number[]): Record<string, number> {
+  return { gini: computeGini(wealth), ...computeInequality(wealth) }
+}
+
+/**
+ * Everything recorded at the end of a generation. Parent–child measures (the
+ * variance decompositions, parent–offspring slopes, IGE, rank-rank slope) are
+ * left out for founders, and the partnered share needs the generation's pairing stats.
+ */
+export function generationRecord(pop: Agent[], pairing: PairingStats | null = null): Record<string, number> {
+  const pgs = pop.map(a => a.polygenicScore ?? a.meanAllele)
+  const record: Record<string, number> = {
+    ...wealthRecord(pop.map(a => a.wealth)),
+    meanWealth:    mean(pop.map(a => a.wealth)),
+    meanEducation: mean(pop.map(a => a.educationScore)),
+    meanEnv:       mean(pop.map(a => a.env)),
+    meanPGS:       mean(pgs),
+    varPGS:        variance(pgs),
+    catastrophe:   catastropheFraction(pop),
+    mutations:     mutationCount(pop),
+  }
+  if (pairing) record.partnered = pairing.fractionPartnered
+  if (pop.some(a => a.parents)) {
+    const { education, wealth, parentOffspring } = computeGenerationDiagnostics(pop)
+    record.geneShare               = education.gene
+    record.eduEnvShare             = education.env
+    record.eduParentWealthShare    = education.parentWealth
+    record.eduNoiseShare           = education.noise
+    record.wealthGeneShare         = wealth.gene
+    record.wealthEnvShare          = wealth.env
+    record.wealthParentWealthShare = wealth.parentWealth
+    record.wealthNoiseShare        = wealth.noise
+    record.poSlope                 = parentOffspring.education
+    record.poSlopeGene             = parentOffspring.gene
+    record.poSlopeEnv              = parentOffspring.env
+    record.poSlopeWealth           = parentOffspring.wealth
+    record.ige = computeIGE(pop)
+    record.rankRankSlope = computeRankRankSlope(pop)
+  }
+  return record
+}
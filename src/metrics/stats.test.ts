@@ -0,0 +1,45 @@
+// src/metrics/stats.test.ts
+import { describe, it, expect } from 'vitest'
+import { mean, variance, pearson, slope, regressionSlopes } from './stats'
+
+describe('Descriptive Statistics - stats.ts', () => {
+  it('should compute the mean and sample variance', () => {
+    expect(mean([1, 2, 3, 4])).toBeCloseTo(2.5)
+    expect(variance([1, 2, 3, 4])).toBeCloseTo(5 / 3)
+    expect(mean([])).toBe(0)
+    expect(variance([7])).toBe(0)
+  })
+
+  it('should return NaN correlations and slopes without variance', () => {
+    expect(pearson([1, 1, 1], [1, 2, 3])).toBeNaN()
+    expect(slope([2, 2, 2], [1, 2, 3])).toBeNaN()
+  })
+
+  it('should recover an exact linear slope', () => {
+    const xs = [1, 2, 3, 4, 5]
+    expect(slope(xs, xs.map(x => 3 * x - 1))).toBeCloseTo(3)
+  })
+
+  describe('regressionSlopes', () => {
+    const x1 = [1, 2, 3, 4, 5, 6, 7, 8]
+    const x2 = [3, 1, 4, 1, 5, 9, 2, 6]
+    const ys = x1.map((x, i) => 2 * x - 3 * x2[i] + 1)
+
+    it('should recover the coefficients of an exact linear model', () => {
+      const [b1, b2] = regressionSlopes(ys, [x1, x2])
+      expect(b1).toBeCloseTo(2)
+      expect(b2).toBeCloseTo(-3)
+    })
+
+    it('should give collinear and constant predictors a zero slope', () => {
+      const doubled = x1.map(x => 2 * x)
+      const constant = x1.map(() => 5)
+      const [b0, b1, b2, b3] = regressionSlopes(ys, [constant, x1, doubled, x2])
+
+      expect(b0).toBe(0)
+      expect(b1).toBeCloseTo(2)
+      expect(b2).toBe(0)
+      expect(b3).toBeCloseTo(-3)
+    })
+  })
+})
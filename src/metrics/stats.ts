@@ -0,0 +1,78 @@
+// src/metrics/stats.ts
+// Small descriptive-statistics helpers shared by the model and the metrics modules.
+
+/** Arithmetic mean (0 for an empty array) */
+export function mean(xs: number[]): number {
+  if (xs.length === 0) return 0
+  let s = 0
+  for (const x of xs) s += x
+  return s / xs.length
+}
+
+/** Sample variance with n - 1 denominator (0 for fewer than two values) */
+export function variance(xs: number[]): number {
+  const n = xs.length
+  if (n < 2) return 0
+  const m = mean(xs)
+  let ss = 0
+  for (const x of xs) ss += (x - m) ** 2
+  return ss / (n - 1)
+}
+
+/** Sample covariance of two equal-length arrays */
+export function covariance(xs: number[], ys: number[]): number {
+  const n = Math.min(xs.length, ys.length)
+  if (n < 2) return 0
+  const mx = mean(xs)
+  const my = mean(ys)
+  let s = 0
+  for (let i = 0; i < n; i++) s += (xs[i] - mx) * (ys[i] - my)
+  return s / (n - 1)
+}
+
+/** Pearson correlation; NaN when either array has no variance */
+export function pearson(xs: number[], ys: number[]): number {
+  const sx = Math.sqrt(variance(xs))
+  const sy = Math.sqrt(variance(ys))
+  if (sx === 0 || sy === 0) return NaN
+  return covariance(xs, ys) / (sx * sy)
+}
+
+/** Least-squares slope of ys on xs; NaN when xs has no variance */
+export function slope(xs: number[], ys: number[]): number {
+  const vx = variance(xs)
+  if (vx === 0) return NaN
+  return covariance(xs, ys) / vx
+}
+
+/**
+ * Least-squares slopes of ys on several predictors at once (intercept implied),
+ * solved from the covariance normal equations by Gauss–Jordan elimination.
+ * Predictors with no variance, or collinear with earlier ones, get a zero slope.
+ */
+export function regressionSlopes(ys: number[], xs: number[][]): number[] {
+  const k = xs.length
+  const A = xs.map(xi => xs.map(xj => covariance(xi, xj)))
+  const M = A.map((row, i) => [...row, covariance(xs[i], ys)])
+  const pivotRow = new Array<number>(k).fill(-1)
+  const EPS = 1e-10
+
+  let r = 0
+  for (let c = 0; c < k && r < k; c++) {
+    let p = r
+    for (let i = r + 1; i < k; i++) {
+      if (Math.abs(M[i][c]) > Math.abs(M[p][c])) p = i
+    }
+    // what is left of the column after removing earlier predictors is (numerically) nothing
+    if (Math.abs(M[p][c]) <= EPS * A[c][c]) continue
+    ;[M[r], M[p]] = [M[p], M[r]]
+    for (let i = 0; i < k; i++) {
+      if (i === r) continue
+      const f = M[i][c] / M[r][c]
+      for (let j = c; j <= k; j++) M[i][j] -= f * M[r][j]
+    }
+    pivotRow[c] = r
+    r++
+  }
+  return pivotRow.map((row, c) => row < 0 ? 0 : M[row][k] / M[row][c])
+}
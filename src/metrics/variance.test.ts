@@ -0,0 +1,121 @@
+// src/metrics/variance.test.ts
+import { describe, it, expect } from 'vitest'
+import {
+  computeVarianceDecomposition,
+  computeParentOffspringSlopes,
+  computeGenerationDiagnostics
+} from './variance'
+import { createRng } from '../rng'
+import {
+  defaultParams,
+  initializePopulation,
+  nextGeneration,
+  type Agent,
+  type Params
+} from '../model'
+
+const makeAgent = (overrides: Partial<Agent>): Agent => ({
+  id: 0, alleles: [0, 0], meanAllele: 0, env: 0, rawenv: 0,
+  educationScore: 0, wealth: 0, parentWealth: 0, parents: null,
+  ...overrides
+})
+
+describe('Variance Diagnostics - variance.ts', () => {
+  describe('computeVarianceDecomposition', () => {
+    it('should attribute an exact additive outcome to its sources', () => {
+      const rng = createRng(1)
+      // independent gene and env; parental wealth plays no part
+      const pop = Array.from({ length: 5000 }, (_, i) => {
+        const gene = rng.normal(0, 1)
+        const env = rng.normal(0, 1)
+        return makeAgent({
+          id: i,
+          meanAllele: gene,
+          env,
+          parentWealth: Math.exp(rng.normal(10, 1)),
+          educationScore: 2 * gene + env
+        })
+      })
+      const d = computeVarianceDecomposition(pop, a => a.educationScore)
+
+      expect(d.total).toBeCloseTo(5, 0)
+      expect(d.gene).toBeCloseTo(0.8, 1)
+      expect(d.env).toBeCloseTo(0.2, 1)
+      expect(d.parentWealth).toBeCloseTo(0, 6)
+      expect(d.noise).toBeCloseTo(0, 6)
+    })
+
+    it('should leave pure noise unexplained', () => {
+      const rng = createRng(2)
+      const pop = Array.from({ length: 5000 }, (_, i) => makeAgent({
+        id: i,
+        meanAllele: rng.normal(0, 1),
+        env: rng.normal(0, 1),
+        parentWealth: 1000 * (i + 1),
+        educationScore: rng.normal(0, 1)
+      }))
+      const d = computeVarianceDecomposition(pop, a => a.educationScore)
+
+      expect(d.noise).toBeGreaterThan(0.99)
+      expect(d.gene + d.env + d.parentWealth + d.noise).toBeCloseTo(1)
+    })
+
+    it('should prefer the polygenic score over meanAllele', () => {
+      const pop = Array.from({ length: 10 }, (_, i) => makeAgent({
+        id: i,
+        meanAllele: (i * 7) % 10,
+        polygenicScore: i,
+        educationScore: i
+      }))
+      expect(computeVarianceDecomposition(pop, a => a.educationScore).gene).toBeCloseTo(1)
+    })
+
+    it('should return zero shares for a constant outcome', () => {
+      const pop = [makeAgent({ id: 0 }), makeAgent({ id: 1, env: 1 })]
+      const d = computeVarianceDecomposition(pop, () => 3)
+      expect(d).toEqual({ total: 0, gene: 0, env: 0, parentWealth: 0, noise: 0 })
+    })
+  })
+
+  describe('computeParentOffspringSlopes', () => {
+    it('should regress children on the mid-parent', () => {
+      const pop = Array.from({ length: 20 }, (_, i) => {
+        const mother = makeAgent({ id: 2 * i, meanAllele: i, env: i, educationScore: i, wealth: 1000 * (i + 1) })
+        const father = makeAgent({ id: 2 * i + 1, meanAllele: i + 2, env: i, educationScore: i, wealth: 1000 * (i + 1) })
+        return makeAgent({
+          id: i,
+          meanAllele: i + 1,        // exactly the mid-parent: slope 1
+          env: 0.5 * i,             // half the mid-parent: slope 0.5
+          educationScore: 3,        // no resemblance: slope 0
+          wealth: 1000 * (i + 1),
+          parents: [mother, father]
+        })
+      })
+      const slopes = computeParentOffspringSlopes(pop)
+
+      expect(slopes.gene).toBeCloseTo(1)
+      expect(slopes.env).toBeCloseTo(0.5)
+      expect(slopes.education).toBeCloseTo(0)
+      expect(slopes.wealth).toBeCloseTo(1)
+    })
+
+    it('should return NaN for the founding generation', () => {
+      const slopes = computeParentOffspringSlopes([makeAgent({ id: 0 }), makeAgent({ id: 1 })])
+      expect(slopes.gene).toBeNaN()
+    })
+  })
+
+  describe('computeGenerationDiagnostics', () => {
+    it('should explain the default education score fully', () => {
+      const params: Params = { ...defaultParams, populationSize: 500 }
+      const rng = createRng(3)
+      const pop = nextGeneration(initializePopulation(params, rng), params, rng)
+      const diagnostics = computeGenerationDiagnostics(pop)
+
+      // education is an exact linear function of gene and env by default
+      expect(diagnostics.education.noise).toBeCloseTo(0, 6)
+      // alleles are passed on, so children resemble their parents genetically
+      expect(diagnostics.parentOffspring.gene).toBeGreaterThan(0.5)
+    })
+  })
+})
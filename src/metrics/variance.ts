@@ -0,0 +1,86 @@
+// src/metrics/variance.ts
+// Nature/nurture diagnostics: variance decompositions and parent–offspring regressions.
+import type { Agent } from '../model'
+import { covariance, mean, regressionSlopes, slope, variance } from './stats'
+
+/** Sources an outcome's variance is attributed to */
+export interface VarianceDecomposition {
+  /** Variance of the outcome */
+  total: number
+  /** Shares of the total (they sum to 1; a collinear source can come out slightly negative) */
+  gene: number
+  env: number
+  parentWealth: number
+  /** Variance left unexplained by the three sources */
+  noise: number
+}
+
+/** Parent–offspring regression slopes, child trait on mid-parent trait */
+export interface ParentOffspringSlopes {
+  gene: number
+  env: number
+  education: number
+  wealth: number
+}
+
+/** Everything the UI tracks about nature and nurture in one generation */
+export interface GenerationDiagnostics {
+  education: VarianceDecomposition
+  /** Decomposition of log wealth */
+  wealth: VarianceDecomposition
+  parentOffspring: ParentOffspringSlopes
+}
+
+const geneValue = (a: Agent) => a.polygenicScore ?? a.meanAllele
+const envValue = (a: Agent) => a.env
+const logWealth = (a: Agent) => Math.log1p(Math.max(0, a.wealth))
+const logParentWealth = (a: Agent) => Math.log1p(Math.max(0, a.parentWealth))
+
+/**
+ * Split the variance of `outcome` between the genetic score, env and log parental
+ * wealth by regressing on all three at once: each source gets its slope × its
+ * covariance with the outcome (Pratt's measure), so the shares add up to R² and
+ * noise takes the rest.
+ */
+export function computeVarianceDecomposition(
+  pop: Agent[],
+  outcome: (a: Agent) => number
+): VarianceDecomposition {
+  const ys = pop.map(outcome)
+  const total = variance(ys)
+  if (total === 0) {
+    return { total, gene: 0, env: 0, parentWealth: 0, noise: 0 }
+  }
+
+  const sources = [geneValue, envValue, logParentWealth].map(f => pop.map(f))
+  const betas = regressionSlopes(ys, sources)
+  const [gene, env, parentWealth] = sources.map((xs, j) => betas[j] * covariance(xs, ys) / total)
+  return { total, gene, env, parentWealth, noise: 1 - gene - env - parentWealth }
+}
+
+/**
+ * Slopes of each child trait on the mean of its parents' snapshots.
+ * Agents without parents (the founding generation) are skipped; NaN when none are left.
+ */
+export function computeParentOffspringSlopes(pop: Agent[]): ParentOffspringSlopes {
+  const kids = pop.filter(a => a.parents && a.parents.length > 0)
+  const regress = (f: (a: Agent) => number) => slope(
+    kids.map(k => mean(k.parents!.map(f))),
+    kids.map(f)
+  )
+  return {
+    gene:      regress(geneValue),
+    env:       regress(envValue),
+    education: regress(a => a.educationScore),
+    wealth:    regress(logWealth),
+  }
+}
+
+/** All per-generation diagnostics for one population */
+export function computeGenerationDiagnostics(pop: Agent[]): GenerationDiagnostics {
+  return {
+    education:       computeVarianceDecomposition(pop, a => a.educationScore),
+    wealth:          computeVarianceDecomposition(pop, logWealth),
+    parentOffspring: computeParentOffspringSlopes(pop),
+  }
+}
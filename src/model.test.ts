@@ -4,7 +4,26 @@ import {
   defaultParams,
   initializePopulation,
   computeEducationScore,
+  computePolygenicScore,
   computeWealthFromScore,
+  computeWealthFromAccumulation,
+  computeLaborIncome,
+  computePercentile,
+  computePercentiles,
+  getSavingsRate,
+  computePopulationWealth,
+  inheritWealth,
+  drawCatastropheLoss,
+  catastropheFraction,
+  divideEstate,
+  fertilityMean,
+  drawNumberOfChildren,
+  resamplePopulation,
+  findSingles,
+  reproduceAlone,
+  computePairingStats,
+  mutateAllele,
+  mutationCount,
   envFromWealth,
   selectMatingPool,
   mate,
@@ -12,6 +31,7 @@ import {
   type Agent,
   type Params
 } from './model'
+import { createRng } from './rng'
 
 describe('Model Core Functions', () => {
   describe('defaultParams', () => {
@@ -23,6 +43,27 @@ describe('Model Core Functions', () => {
       expect(defaultParams.financeNoise).toBe(0.1)
       expect(defaultParams.homophily.gene).toBe(0.0)
       expect(defaultParams.homophily.env).toBe(0.0)
+      expect(defaultParams.accumulationModel).toBe(false)
+      expect(defaultParams.capitalReturnRate).toBe(0.05)
+      expect(defaultParams.wageRate).toBe(1.0)
+      expect(defaultParams.periodsPerGeneration).toBe(1)
+      expect(defaultParams.inheritanceRate).toBe(1.0)
+      expect(defaultParams.catastropheDistribution).toBe('beta')
+      expect(defaultParams.inheritanceDivision).toBe('equal')
+      expect(defaultParams.fertility.model).toBe('fixed')
+      expect(defaultParams.fertility.meanChildren).toBe(2)
+      expect(defaultParams.fertility.capPopulation).toBe(false)
+      expect(defaultParams.partnershipRate).toBe(1.0)
+      expect(defaultParams.singleFertility).toBe(0)
+      expect(defaultParams.matingMode).toBe('monogamous')
+      expect(defaultParams.genetics.nLoci).toBe(1)
+      expect(defaultParams.genetics.dominance).toBe(0)
+      expect(defaultParams.genetics.recombinationRate).toBe(0.5)
+      expect(defaultParams.genetics.mutationRate).toBe(0.01)
+      expect(defaultParams.genetics.mutationKernel).toBe('redraw')
+      expect(defaultParams.gxeWeight).toBe(0)
+      expect(defaultParams.educationNoiseStd).toBe(0)
+      expect(defaultParams.passiveRGE).toBe(0)
     })
 
     it('should have parameters in valid ranges', () => {
@@ -69,6 +110,22 @@ describe('Model Core Functions', () => {
       })
     })
 
+    it('should give each agent 2 alleles per locus with a unit-scale polygenic score', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 4000,
+        genetics: { ...defaultParams.genetics, nLoci: 50 }
+      }
+      const pop = initializePopulation(params, createRng(8))
+      const scores = pop.map(a => a.polygenicScore!)
+      const m = scores.reduce((s, x) => s + x, 0) / scores.length
+      const v = scores.reduce((s, x) => s + (x - m) ** 2, 0) / (scores.length - 1)
+
+      pop.forEach(agent => expect(agent.alleles).toHaveLength(100))
+      // default effects 1/√nLoci keep the variance of a single mean allele, 1/2
+      expect(v).toBeCloseTo(0.5, 1)
+    })
+
     it('should set meanAllele as average of alleles', () => {
       const params = { ...defaultParams, populationSize: 10 }
       const pop = initializePopulation(params)
@@ -145,6 +202,65 @@ describe('Model Core Functions', () => {
       const score = computeEducationScore(agent, params)
       expect(score).toBeCloseTo(0.7 * 1.0 + 0.3 * 0.0)
     })
+
+    it('should add a gene-environment interaction', () => {
+      const agent: Agent = {
+        id: 0, alleles: [1, 1], meanAllele: 1, env: 2, rawenv: 2,
+        educationScore: 0, wealth: 0, parentWealth: 0, parents: null
+      }
+      const params: Params = { ...defaultParams, geneEnvWeight: 0.5, gxeWeight: 0.25 }
+
+      expect(computeEducationScore(agent, params)).toBeCloseTo(0.5 * 1 + 0.5 * 2 + 0.25 * 1 * 2)
+    })
+
+    it('should add non-shared environmental noise', () => {
+      const agent: Agent = {
+        id: 0, alleles: [0, 0], meanAllele: 0, env: 0, rawenv: 0,
+        educationScore: 0, wealth: 0, parentWealth: 0, parents: null
+      }
+      const params: Params = { ...defaultParams, educationNoiseStd: 0.3 }
+      const rng = createRng(12)
+      const scores = Array.from({ length: 5000 }, () => computeEducationScore(agent, params, rng))
+      const m = scores.reduce((s, x) => s + x, 0) / scores.length
+      const sd = Math.sqrt(scores.reduce((s, x) => s + (x - m) ** 2, 0) / (scores.length - 1))
+
+      expect(m).toBeCloseTo(0, 1)
+      expect(sd).toBeCloseTo(0.3, 1)
+    })
+  })
+
+  describe('computePolygenicScore', () => {
+    it('should reduce to the mean allele for one additive locus', () => {
+      expect(computePolygenicScore([0.4, 0.6], defaultParams)).toBeCloseTo(0.5)
+    })
+
+    it('should weight loci by their effect sizes', () => {
+      const params: Params = {
+        ...defaultParams,
+        genetics: { ...defaultParams.genetics, nLoci: 2, effectSizes: [1, 0.5] }
+      }
+      // haplotype 0 = [1, 2], haplotype 1 = [3, 4]
+      expect(computePolygenicScore([1, 2, 3, 4], params)).toBeCloseTo(1 * 2 + 0.5 * 3)
+    })
+
+    it('should let the higher or lower allele dominate', () => {
+      const genetics = defaultParams.genetics
+      const high: Params = { ...defaultParams, genetics: { ...genetics, dominance: 1 } }
+      const low: Params = { ...defaultParams, genetics: { ...genetics, dominance: -1 } }
+
+      expect(computePolygenicScore([0.2, 1.0], high)).toBeCloseTo(1.0)
+      expect(computePolygenicScore([0.2, 1.0], low)).toBeCloseTo(0.2)
+    })
+
+    it('should feed the education score in place of meanAllele', () => {
+      const agent: Agent = {
+        id: 0, alleles: [0, 0], meanAllele: 0, polygenicScore: 2, env: 0, rawenv: 0,
+        educationScore: 0, wealth: 0, parentWealth: 0, parents: null
+      }
+      const params: Params = { ...defaultParams, geneEnvWeight: 1.0 }
+
+      expect(computeEducationScore(agent, params)).toBeCloseTo(2)
+    })
   })
 
   describe('envFromWealth', () => {
@@ -195,6 +311,27 @@ describe('Model Core Functions', () => {
       // Most should be different due to noise
       expect(uniqueEnvs.size).toBeGreaterThan(10)
     })
+
+    it('should shift env by the parents\' genes under passive rGE', () => {
+      const parentOf = (pgs: number): Agent => ({
+        id: 0, alleles: [pgs, pgs], meanAllele: pgs, polygenicScore: pgs, env: 0, rawenv: 0,
+        educationScore: 0, wealth: 0, parentWealth: 0, parents: null
+      })
+      const makeChild = (pgs: number): Agent => ({
+        id: 1, alleles: [0, 0], meanAllele: 0, env: 0, rawenv: 0,
+        educationScore: 0, wealth: 0, parentWealth: 5000, // same parent wealth for all
+        parents: [parentOf(pgs), parentOf(pgs)]
+      })
+      const agents = [makeChild(-1), makeChild(0), makeChild(1)]
+
+      envFromWealth(agents, { ...defaultParams, envNoiseStd: 0 })
+      const baseline = agents.map(a => a.env)
+      envFromWealth(agents, { ...defaultParams, envNoiseStd: 0, passiveRGE: 0.5 })
+
+      expect(agents[0].env - baseline[0]).toBeCloseTo(-0.5)
+      expect(agents[1].env - baseline[1]).toBeCloseTo(0)
+      expect(agents[2].env - baseline[2]).toBeCloseTo(0.5)
+    })
   })
 
   describe('computeWealthFromScore', () => {
@@ -244,6 +381,415 @@ describe('Model Core Functions', () => {
     })
   })
 
+  describe('computeWealthFromAccumulation', () => {
+    const makeAgent = (educationScore: number, previousWealth: number): Agent => ({
+      id: 0,
+      alleles: [0, 0],
+      meanAllele: 0,
+      env: 0,
+      rawenv: 0,
+      educationScore,
+      wealth: 0,
+      parentWealth: 0,
+      parents: null,
+      previousWealth
+    })
+
+    it('should add savings to previous wealth', () => {
+      const agent = makeAgent(0.5, 100000)
+      const params: Params = { ...defaultParams, accumulationModel: true }
+      const wealth = computeWealthFromAccumulation(agent, params, 0.15)
+
+      expect(wealth).toBeCloseTo(100000 + agent.savings!)
+    })
+
+    it('should earn capitalReturnRate on previous wealth', () => {
+      const agent = makeAgent(0.5, 100000)
+      const params: Params = { ...defaultParams, accumulationModel: true, capitalReturnRate: 0.07 }
+      computeWealthFromAccumulation(agent, params, 0.15)
+
+      expect(agent.capitalIncome).toBeCloseTo(7000)
+    })
+
+    it('should save the given rate of total income', () => {
+      const agent = makeAgent(0.5, 100000)
+      const params: Params = { ...defaultParams, accumulationModel: true }
+      computeWealthFromAccumulation(agent, params, 0.3)
+
+      expect(agent.savings).toBeCloseTo(0.3 * (agent.laborIncome! + agent.capitalIncome!))
+    })
+
+    it('should reduce to labor income only when capitalReturnRate is zero', () => {
+      const agent = makeAgent(0.5, 100000)
+      const params: Params = { ...defaultParams, accumulationModel: true, capitalReturnRate: 0 }
+      computeWealthFromAccumulation(agent, params, 0.15)
+
+      expect(agent.capitalIncome).toBe(0)
+      expect(agent.savings).toBeCloseTo(0.15 * agent.laborIncome!)
+    })
+
+    it('should let capital losses reduce wealth in full', () => {
+      const agent = makeAgent(0, 1000000)
+      const params: Params = { ...defaultParams, accumulationModel: true, capitalReturnRate: -0.5 }
+      const wealth = computeWealthFromAccumulation(agent, params, 0.15)
+
+      expect(agent.savings).toBeCloseTo(agent.laborIncome! + agent.capitalIncome!)
+      expect(wealth).toBeLessThan(1000000)
+    })
+
+    it('should pay more labor income for more education', () => {
+      const params: Params = { ...defaultParams, financeNoise: 0 }
+      const low = computeLaborIncome(makeAgent(-1, 0), params)
+      const high = computeLaborIncome(makeAgent(1, 0), params)
+
+      expect(high).toBeGreaterThan(low)
+    })
+
+    it('should scale labor income by wageRate', () => {
+      const agent = makeAgent(0, 0)
+      const base = computeLaborIncome(agent, { ...defaultParams, financeNoise: 0 })
+      const doubled = computeLaborIncome(agent, { ...defaultParams, financeNoise: 0, wageRate: 2 })
+
+      expect(doubled).toBeCloseTo(2 * base)
+    })
+  })
+
+  describe('computePercentile', () => {
+    it('should return the share of values strictly below', () => {
+      const sorted = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+
+      expect(computePercentile(1, sorted)).toBe(0)
+      expect(computePercentile(3, sorted)).toBeCloseTo(0.2)
+      expect(computePercentile(10, sorted)).toBeCloseTo(0.9)
+      expect(computePercentile(11, sorted)).toBe(1)
+    })
+
+    it('should give tied values the same percentile', () => {
+      expect(computePercentile(2, [1, 2, 2, 2, 3])).toBeCloseTo(0.2)
+    })
+  })
+
+  describe('computePercentiles', () => {
+    it('should match computePercentile for every value', () => {
+      const values = [5, 1, 9, 3, 3, 7, 2, 8, 3, 0]
+      const sorted = [...values].sort((a, b) => a - b)
+      const percentiles = computePercentiles(values)
+
+      values.forEach((v, i) => {
+        expect(percentiles[i]).toBeCloseTo(computePercentile(v, sorted))
+      })
+    })
+  })
+
+  describe('getSavingsRate', () => {
+    it('should use the rate of each percentile tier', () => {
+      const params: Params = { ...defaultParams }
+
+      expect(getSavingsRate(0.1, params)).toBe(params.savingsRateBottom)
+      expect(getSavingsRate(0.5, params)).toBe(params.savingsRateMiddle)
+      expect(getSavingsRate(0.9, params)).toBe(params.savingsRateUpper)
+      expect(getSavingsRate(0.995, params)).toBe(params.savingsRateTop)
+    })
+
+    it('should respect configurable cutoffs', () => {
+      const params: Params = {
+        ...defaultParams,
+        savingsCutoffBottom: 0.5,
+        savingsCutoffMiddle: 0.6,
+        savingsCutoffUpper: 0.7
+      }
+
+      expect(getSavingsRate(0.4, params)).toBe(params.savingsRateBottom)
+      expect(getSavingsRate(0.55, params)).toBe(params.savingsRateMiddle)
+      expect(getSavingsRate(0.65, params)).toBe(params.savingsRateUpper)
+      expect(getSavingsRate(0.75, params)).toBe(params.savingsRateTop)
+    })
+  })
+
+  describe('inheritWealth', () => {
+    const heir: Agent = {
+      id: 0, alleles: [0, 0], meanAllele: 0, env: 0, rawenv: 0,
+      educationScore: 0, wealth: 0, parentWealth: 200000, parents: null
+    }
+
+    it('should start from the child\'s own share of the estate', () => {
+      const agent = { ...heir, inheritance: 50000 }
+      const params: Params = { ...defaultParams, inheritanceRate: 1, catastropheRate: 0 }
+
+      expect(inheritWealth(agent, params)).toBeCloseTo(50000)
+    })
+
+    it('should pass on inheritanceRate of parent wealth without catastrophes', () => {
+      const agent = { ...heir }
+      const params: Params = { ...defaultParams, inheritanceRate: 0.8, catastropheRate: 0 }
+
+      expect(inheritWealth(agent, params)).toBeCloseTo(160000)
+      expect(agent.catastrophe).toBe(false)
+    })
+
+    it('should apply a fixed catastrophe loss', () => {
+      const agent = { ...heir }
+      const params: Params = {
+        ...defaultParams,
+        inheritanceRate: 0.8,
+        catastropheRate: 1,
+        catastropheSeverity: 0.5,
+        catastropheDistribution: 'fixed'
+      }
+
+      expect(inheritWealth(agent, params)).toBeCloseTo(80000)
+      expect(agent.catastrophe).toBe(true)
+    })
+
+    it('should draw beta losses with mean catastropheSeverity', () => {
+      const rng = createRng(5)
+      const params: Params = { ...defaultParams, catastropheSeverity: 0.3, catastropheDistribution: 'beta' }
+      const n = 50000
+      let total = 0
+      for (let i = 0; i < n; i++) total += drawCatastropheLoss(params, rng)
+
+      expect(total / n).toBeCloseTo(0.3, 2)
+    })
+
+    it('should strike about catastropheRate of inheritances', () => {
+      const rng = createRng(9)
+      const params: Params = { ...defaultParams, catastropheRate: 0.25 }
+      const pop = Array(4000).fill(null).map(() => ({ ...heir }))
+      pop.forEach(a => inheritWealth(a, params, rng))
+
+      expect(catastropheFraction(pop)).toBeCloseTo(0.25, 1)
+    })
+  })
+
+  describe('divideEstate', () => {
+    it('should split equally by default', () => {
+      expect(divideEstate(90000, 3, defaultParams)).toEqual([30000, 30000, 30000])
+    })
+
+    it('should give everything to the eldest under primogeniture', () => {
+      const params: Params = { ...defaultParams, inheritanceDivision: 'primogeniture' }
+
+      expect(divideEstate(90000, 3, params)).toEqual([90000, 0, 0])
+    })
+
+    it('should weight children by inheritanceWeightDecay', () => {
+      const params: Params = { ...defaultParams, inheritanceDivision: 'weighted', inheritanceWeightDecay: 0.5 }
+      const shares = divideEstate(70000, 3, params)
+
+      expect(shares[0]).toBeCloseTo(40000)
+      expect(shares[1]).toBeCloseTo(20000)
+      expect(shares[2]).toBeCloseTo(10000)
+    })
+
+    it('should conserve the estate under every rule', () => {
+      for (const inheritanceDivision of ['equal', 'primogeniture', 'weighted'] as const) {
+        for (let n = 1; n <= 6; n++) {
+          const params: Params = { ...defaultParams, inheritanceDivision, inheritanceWeightDecay: 0.7 }
+          const total = divideEstate(123456, n, params).reduce((s, x) => s + x, 0)
+
+          expect(total).toBeCloseTo(123456)
+        }
+      }
+    })
+
+    it('should return no shares for no children', () => {
+      expect(divideEstate(1000, 0, defaultParams)).toEqual([])
+    })
+  })
+
+  describe('fertility', () => {
+    const withFertility = (fertility: Partial<Params['fertility']>): Params => ({
+      ...defaultParams,
+      fertility: { ...defaultParams.fertility, ...fertility }
+    })
+
+    it('should expect meanChildren at the median wealth and zero education', () => {
+      expect(fertilityMean(0.5, 0, withFertility({ wealthGradient: -1, educationGradient: 0.5 }))).toBeCloseTo(2)
+    })
+
+    it('should lower fertility for richer pairs with a negative wealth gradient', () => {
+      const params = withFertility({ wealthGradient: -1 })
+
+      expect(fertilityMean(0.9, 0, params)).toBeLessThan(fertilityMean(0.1, 0, params))
+    })
+
+    it('should shift fertility with education', () => {
+      const params = withFertility({ educationGradient: -0.5 })
+
+      expect(fertilityMean(0.5, 1, params)).toBeLessThan(fertilityMean(0.5, -1, params))
+    })
+
+    it('should always draw two children under the default fixed model', () => {
+      for (let i = 0; i < 100; i++) {
+        expect(drawNumberOfChildren(2, defaultParams)).toBe(2)
+      }
+    })
+
+    it('should randomly round fractional means under the fixed model', () => {
+      const rng = createRng(3)
+      const n = 10000
+      const draws = Array.from({ length: n }, () => drawNumberOfChildren(2.3, defaultParams, rng))
+
+      draws.forEach(k => expect([2, 3]).toContain(k))
+      expect(draws.reduce((s, k) => s + k, 0) / n).toBeCloseTo(2.3, 1)
+    })
+
+    it('should draw Poisson family sizes with the requested mean', () => {
+      const rng = createRng(4)
+      const params = withFertility({ model: 'poisson' })
+      const n = 10000
+      const draws = Array.from({ length: n }, () => drawNumberOfChildren(2, params, rng))
+
+      expect(draws.reduce((s, k) => s + k, 0) / n).toBeCloseTo(2, 1)
+    })
+
+    it('should overdisperse family sizes under the negative binomial model', () => {
+      const rng = createRng(6)
+      const params = withFertility({ model: 'negativeBinomial', dispersion: 1 })
+      const n = 20000
+      const draws = Array.from({ length: n }, () => drawNumberOfChildren(2, params, rng))
+      const mean = draws.reduce((s, k) => s + k, 0) / n
+      const variance = draws.reduce((s, k) => s + (k - mean) ** 2, 0) / (n - 1)
+
+      expect(mean).toBeCloseTo(2, 1)
+      expect(variance).toBeGreaterThan(1.5 * mean)
+    })
+  })
+
+  describe('resamplePopulation', () => {
+    const makePop = (n: number): Agent[] => Array(n).fill(null).map((_, i) => ({
+      id: i, alleles: [0, 0] as [number, number], meanAllele: 0, env: 0, rawenv: 0,
+      educationScore: 0, wealth: i, parentWealth: 0, parents: null
+    }))
+
+    it('should shrink to a subset without repeats', () => {
+      const out = resamplePopulation(makePop(50), 20)
+
+      expect(out).toHaveLength(20)
+      expect(new Set(out.map(a => a.wealth)).size).toBe(20)
+    })
+
+    it('should grow by copying existing agents', () => {
+      const pop = makePop(5)
+      const wealths = pop.map(a => a.wealth)
+      const out = resamplePopulation(pop, 12)
+
+      expect(out).toHaveLength(12)
+      out.forEach(a => expect(wealths).toContain(a.wealth))
+    })
+
+    it('should conserve total inheritance when growing', () => {
+      const pop = makePop(5).map(a => ({ ...a, inheritance: 100 * (a.id + 1) }))
+      const out = resamplePopulation(pop, 12, createRng(1))
+
+      expect(out.reduce((s, a) => s + a.inheritance!, 0)).toBeCloseTo(1500)
+    })
+
+    it('should conserve total inheritance when shrinking', () => {
+      const pop = makePop(50).map(a => ({ ...a, inheritance: 10 * (a.id + 1) }))
+      const total = pop.reduce((s, a) => s + a.inheritance!, 0)
+      const out = resamplePopulation(pop, 20, createRng(2))
+
+      expect(out.reduce((s, a) => s + a.inheritance!, 0)).toBeCloseTo(total)
+    })
+
+    it('should conserve total inheritance through a capped generation', () => {
+      // the same seed gives the same children before the cap is applied
+      const totalInheritance = (pop: Agent[]) => pop.reduce((s, a) => s + a.inheritance!, 0)
+      for (const meanChildren of [1, 3]) {
+        const params: Params = {
+          ...defaultParams,
+          populationSize: 60,
+          fertility: { ...defaultParams.fertility, model: 'poisson', meanChildren }
+        }
+        const capped: Params = { ...params, fertility: { ...params.fertility, capPopulation: true } }
+        const parents = initializePopulation(params, createRng(10))
+        const free = nextGeneration(parents, params, createRng(meanChildren))
+        const resampled = nextGeneration(parents, capped, createRng(meanChildren))
+
+        expect(resampled).toHaveLength(60)
+        expect(free.length).not.toBe(60)
+        expect(totalInheritance(resampled)).toBeCloseTo(totalInheritance(free))
+      }
+    })
+
+    it('should renumber ids', () => {
+      const out = resamplePopulation(makePop(5), 12)
+
+      expect(out.map(a => a.id)).toEqual([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11])
+    })
+  })
+
+  describe('computePopulationWealth', () => {
+    const makePop = (n: number): Agent[] => Array(n).fill(null).map((_, i) => ({
+      id: i, alleles: [0, 0] as [number, number], meanAllele: 0, env: 0, rawenv: 0,
+      educationScore: 0, wealth: 0, parentWealth: (i + 1) * 1000, parents: null
+    }))
+
+    it('should use the sampling engine by default', () => {
+      const pop = makePop(10)
+      computePopulationWealth(pop, defaultParams)
+
+      pop.forEach(agent => {
+        expect(agent.laborIncome).toBeUndefined()
+        expect(agent.wealth).toBeGreaterThan(0)
+      })
+    })
+
+    it('should use the accumulation engine when accumulationModel is set', () => {
+      const pop = makePop(10)
+      computePopulationWealth(pop, { ...defaultParams, accumulationModel: true })
+
+      pop.forEach(agent => {
+        expect(agent.laborIncome).toBeGreaterThan(0)
+        expect(agent.wealth).toBeCloseTo(agent.previousWealth! + agent.savings!)
+      })
+    })
+
+    it('should report a single period under the sampling engine', () => {
+      const periods: number[] = []
+      computePopulationWealth(makePop(10), { ...defaultParams, periodsPerGeneration: 30 },
+        undefined, (_, t) => periods.push(t))
+
+      expect(periods).toEqual([1])
+    })
+
+    it('should compound periodsPerGeneration annual periods', () => {
+      const params: Params = { ...defaultParams, accumulationModel: true, periodsPerGeneration: 5 }
+      const periods: number[] = []
+      const meanWealth: number[] = []
+      computePopulationWealth(makePop(10), params, undefined, (pop, t) => {
+        periods.push(t)
+        meanWealth.push(pop.reduce((s, a) => s + a.wealth, 0) / pop.length)
+      })
+
+      expect(periods).toEqual([1, 2, 3, 4, 5])
+      for (let t = 1; t < meanWealth.length; t++) {
+        expect(meanWealth[t]).toBeGreaterThan(meanWealth[t - 1])
+      }
+    })
+
+    it('should apply savings tiers by inherited wealth percentile', () => {
+      const pop = makePop(100)
+      const params: Params = {
+        ...defaultParams,
+        accumulationModel: true,
+        savingsRateBottom: 0.01,
+        savingsRateMiddle: 0.02,
+        savingsRateUpper: 0.03,
+        savingsRateTop: 0.04
+      }
+      computePopulationWealth(pop, params)
+
+      const sorted = pop.map(a => a.previousWealth!).sort((a, b) => a - b)
+      pop.forEach(agent => {
+        const rate = agent.savings! / (agent.laborIncome! + agent.capitalIncome!)
+        const expected = getSavingsRate(computePercentile(agent.previousWealth!, sorted), params)
+        expect(rate).toBeCloseTo(expected)
+      })
+    })
+  })
+
   describe('selectMatingPool', () => {
     it('should create N/2 pairs from N agents', () => {
       const agents: Agent[] = Array(20).fill(null).map((_, i) => ({
@@ -321,6 +867,273 @@ describe('Model Core Functions', () => {
     })
   })
 
+  describe('marriage market', () => {
+    const makePop = (n: number): Agent[] => Array(n).fill(null).map((_, i) => ({
+      id: i,
+      alleles: [i / n, i / n] as [number, number],
+      meanAllele: i / n,
+      env: (i % 7) / 7,
+      rawenv: 0,
+      educationScore: 0,
+      wealth: 1000 * (i + 1),
+      parentWealth: 0,
+      parents: null
+    }))
+
+    it('should never reuse an agent across pairs', () => {
+      const pop = makePop(100)
+      for (const h of [0, 0.5, 1]) {
+        const pairs = selectMatingPool(pop, { ...defaultParams, homophily: { gene: h, env: h } })
+        const ids = pairs.flatMap(p => [p.a.id, p.b.id])
+
+        expect(new Set(ids).size).toBe(ids.length)
+      }
+    })
+
+    it('should keep pairs disjoint under homophily with tied traits', () => {
+      // every agent shares its (meanAllele, env) with several others
+      const pop = makePop(300).map((a, i) => ({ ...a, meanAllele: (i % 5) / 5 }))
+      for (const seed of [1, 2, 3]) {
+        const pairs = selectMatingPool(
+          pop,
+          { ...defaultParams, homophily: { gene: 0.9, env: 0.9 } },
+          createRng(seed)
+        )
+        const ids = pairs.flatMap(p => [p.a.id, p.b.id])
+
+        expect(pairs).toHaveLength(150)
+        expect(new Set(ids).size).toBe(ids.length)
+      }
+    })
+
+    it('should reuse mates in sampled mode but never pair an agent with itself', () => {
+      const pop = makePop(200)
+      for (const h of [0, 1]) {
+        const pairs = selectMatingPool(
+          pop,
+          { ...defaultParams, matingMode: 'sampled', homophily: { gene: h, env: h } },
+          createRng(5)
+        )
+        const ids = pairs.flatMap(p => [p.a.id, p.b.id])
+
+        expect(pairs).toHaveLength(100)
+        expect(pairs.every(p => p.a !== p.b)).toBe(true)
+        expect(new Set(ids).size).toBeLessThan(ids.length)
+      }
+    })
+
+    it('should partner about partnershipRate of agents', () => {
+      const pop = makePop(2000)
+      const pairs = selectMatingPool(pop, { ...defaultParams, partnershipRate: 0.6 }, createRng(21))
+      const stats = computePairingStats(pop, pairs)
+
+      expect(stats.fractionPartnered).toBeGreaterThan(0.55)
+      expect(stats.fractionPartnered).toBeLessThan(0.65)
+    })
+
+    it('should leave everyone single when partnershipRate is zero', () => {
+      const pop = makePop(20)
+      const pairs = selectMatingPool(pop, { ...defaultParams, partnershipRate: 0 })
+
+      expect(pairs).toHaveLength(0)
+      expect(findSingles(pop, pairs)).toHaveLength(20)
+    })
+
+    it('should find the agents left out of pairs', () => {
+      const pop = makePop(11)
+      const pairs = selectMatingPool(pop, defaultParams)
+      const singles = findSingles(pop, pairs)
+
+      expect(singles).toHaveLength(1)
+      expect(pairs.some(p => p.a === singles[0] || p.b === singles[0])).toBe(false)
+    })
+
+    it('should report strong spousal correlations under strict homophily', () => {
+      const pop = makePop(400)
+      const random = computePairingStats(pop, selectMatingPool(pop, defaultParams, createRng(2)))
+      const strict = computePairingStats(
+        pop,
+        selectMatingPool(pop, { ...defaultParams, homophily: { gene: 1, env: 1 } }, createRng(2))
+      )
+
+      expect(strict.spousalCorrelation.meanAllele).toBeGreaterThan(0.8)
+      expect(strict.spousalCorrelation.meanAllele).toBeGreaterThan(random.spousalCorrelation.meanAllele)
+      expect(Math.abs(random.spousalCorrelation.meanAllele)).toBeLessThan(0.3)
+    })
+
+    it('should pair dissimilar agents under negative homophily', () => {
+      const pop = makePop(400)
+      const pairs = selectMatingPool(pop, { ...defaultParams, homophily: { gene: -1, env: 0 } }, createRng(2))
+      const ids = pairs.flatMap(p => [p.a.id, p.b.id])
+      const stats = computePairingStats(pop, pairs)
+
+      expect(new Set(ids).size).toBe(ids.length)
+      expect(stats.spousalCorrelation.meanAllele).toBeLessThan(-0.3)
+    })
+
+    it('should mix assortative and disassortative dimensions', () => {
+      const pop = makePop(400)
+      const stats = computePairingStats(
+        pop,
+        selectMatingPool(pop, { ...defaultParams, homophily: { gene: 1, env: -1 } }, createRng(3))
+      )
+
+      expect(stats.spousalCorrelation.meanAllele).toBeGreaterThan(0.8)
+      expect(stats.spousalCorrelation.env).toBeLessThan(-0.5)
+    })
+
+    it('should match on education and wealth when those traits are weighted', () => {
+      // education and wealth are permutations of the id, unrelated to meanAllele
+      const pop = makePop(1000).map((a, i) => ({
+        ...a,
+        educationScore: ((i * 37) % 1000) / 1000,
+        wealth: 1000 * (1 + (i * 53) % 1000)
+      }))
+      const params: Params = {
+        ...defaultParams,
+        homophily: { gene: 0, env: 0, education: 1, wealth: 1 }
+      }
+      const pairs = selectMatingPool(pop, params, createRng(4))
+      const ids = pairs.flatMap(p => [p.a.id, p.b.id])
+      const stats = computePairingStats(pop, pairs)
+
+      expect(new Set(ids).size).toBe(ids.length)
+      expect(stats.spousalCorrelation.education).toBeGreaterThan(0.3)
+      expect(stats.spousalCorrelation.wealth).toBeGreaterThan(0.25)
+      expect(Math.abs(stats.spousalCorrelation.meanAllele)).toBeLessThan(0.2)
+    })
+
+    it('should always choose by similarity when a single non-gene trait has weight 1', () => {
+      const pop = makePop(1000).map((a, i) => ({ ...a, educationScore: ((i * 37) % 1000) / 1000 }))
+      const params: Params = { ...defaultParams, homophily: { gene: 0, env: 0, education: 1 } }
+      const stats = computePairingStats(pop, selectMatingPool(pop, params, createRng(5)))
+
+      // one random choice in three (the old dilution by gene and env) would cap this near 0.33
+      expect(stats.spousalCorrelation.education).toBeGreaterThan(0.9)
+    })
+
+    it('should always choose by similarity when gene alone has weight 1', () => {
+      // env is unrelated to meanAllele, so only the gene weight can align partners
+      const pop = makePop(1000)
+      const params: Params = { ...defaultParams, homophily: { gene: 1, env: 0 } }
+      const stats = computePairingStats(pop, selectMatingPool(pop, params, createRng(6)))
+
+      // half the choices at random (the old gene–env pairing) would cap this near 0.5
+      expect(stats.spousalCorrelation.meanAllele).toBeGreaterThan(0.9)
+    })
+
+    it('should let singles have children alone', () => {
+      const parent = makePop(2)[1]
+      const kids = reproduceAlone(parent, defaultParams, 10, undefined, 3)
+
+      expect(kids).toHaveLength(3)
+      kids.forEach(kid => {
+        expect(kid.parents).toHaveLength(1)
+        expect(kid.parents![0].id).toBe(parent.id)
+        expect(kid.parentWealth).toBe(parent.wealth)
+      })
+      expect(kids.reduce((s, k) => s + k.inheritance!, 0)).toBeCloseTo(parent.wealth)
+    })
+
+    it('should keep singles childless by default', () => {
+      const params: Params = { ...defaultParams, populationSize: 40, partnershipRate: 0.5 }
+      const pop = initializePopulation(params)
+      let partnered = 0
+      const nextPop = nextGeneration(pop, params, undefined, {
+        onPairing: pairs => { partnered = 2 * pairs.length }
+      })
+
+      expect(nextPop).toHaveLength(partnered)
+    })
+
+    it('should add children of singles when singleFertility > 0', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 40,
+        partnershipRate: 0.5,
+        singleFertility: 0.5
+      }
+      const pop = initializePopulation(params)
+      let pairCount = 0
+      let singleCount = 0
+      const nextPop = nextGeneration(pop, params, undefined, {
+        onPairing: (pairs, oldPop) => {
+          pairCount = pairs.length
+          singleCount = oldPop.length - 2 * pairs.length
+        }
+      })
+
+      // fixed model: 2 children per pair, 1 per single
+      expect(nextPop).toHaveLength(2 * pairCount + singleCount)
+    })
+  })
+
+  describe('mutation', () => {
+    const nLoci = 10
+    const parent: Agent = {
+      id: 0,
+      alleles: Array.from({ length: 2 * nLoci }, (_, j) => j),
+      meanAllele: 0, env: 0, rawenv: 0, educationScore: 0,
+      wealth: 1000, parentWealth: 0, parents: null
+    }
+    const withGenetics = (genetics: Partial<Params['genetics']>): Params => ({
+      ...defaultParams,
+      genetics: { ...defaultParams.genetics, nLoci, ...genetics }
+    })
+    const isParental = (x: number) => parent.alleles.includes(x)
+
+    it('should copy alleles exactly with the none kernel', () => {
+      const params = withGenetics({ mutationRate: 1, mutationKernel: 'none' })
+      const kids = mate({ a: parent, b: parent }, params, 0, createRng(1), 10)
+
+      kids.forEach(kid => {
+        expect(kid.mutations).toBe(0)
+        expect(kid.alleles.every(isParental)).toBe(true)
+      })
+      expect(mutationCount(kids)).toBe(0)
+    })
+
+    it('should mutate and count every allele when mutationRate is one', () => {
+      const params = withGenetics({ mutationRate: 1, mutationKernel: 'redraw' })
+      const kids = mate({ a: parent, b: parent }, params, 0, createRng(2), 3)
+
+      kids.forEach(kid => {
+        expect(kid.mutations).toBe(2 * nLoci)
+        expect(kid.alleles.some(isParental)).toBe(false)
+      })
+      expect(mutationCount(kids)).toBe(3 * 2 * nLoci)
+    })
+
+    it('should mutate at about mutationRate', () => {
+      const params = withGenetics({ mutationRate: 0.1 })
+      const kids = mate({ a: parent, b: parent }, params, 0, createRng(3), 500)
+      const rate = mutationCount(kids) / (500 * 2 * nLoci)
+
+      expect(rate).toBeGreaterThan(0.08)
+      expect(rate).toBeLessThan(0.12)
+    })
+
+    it('should perturb rather than reset alleles with the gaussian kernel', () => {
+      const params = withGenetics({ mutationKernel: 'gaussian', mutationStd: 0.1 })
+      const rng = createRng(4)
+      const steps = Array.from({ length: 5000 }, () => mutateAllele(7, params, rng) - 7)
+      const m = steps.reduce((s, x) => s + x, 0) / steps.length
+      const sd = Math.sqrt(steps.reduce((s, x) => s + (x - m) ** 2, 0) / (steps.length - 1))
+
+      expect(m).toBeCloseTo(0, 2)
+      expect(sd).toBeCloseTo(0.1, 2)
+    })
+
+    it('should redraw alleles from N(0, 1) with the redraw kernel', () => {
+      const params = withGenetics({ mutationKernel: 'redraw' })
+      const rng = createRng(5)
+      const draws = Array.from({ length: 5000 }, () => mutateAllele(7, params, rng))
+      const m = draws.reduce((s, x) => s + x, 0) / draws.length
+
+      expect(m).toBeCloseTo(0, 1)
+    })
+  })
+
   describe('mate', () => {
     it('should produce 2 children', () => {
       const parentA: Agent = {
@@ -390,6 +1203,56 @@ describe('Model Core Functions', () => {
       })
     })
 
+    it('should pass on whole haplotypes without recombination', () => {
+      const nLoci = 20
+      const params: Params = {
+        ...defaultParams,
+        genetics: { ...defaultParams.genetics, nLoci, recombinationRate: 0 }
+      }
+      // every allele value is unique, so its origin can be traced
+      const makeParent = (id: number): Agent => ({
+        id,
+        alleles: Array.from({ length: 2 * nLoci }, (_, j) => id * 100 + j),
+        meanAllele: 0, env: 0, rawenv: 0, educationScore: 0,
+        wealth: 1000, parentWealth: 0, parents: null
+      })
+      const parentA = makeParent(1)
+      const parentB = makeParent(2)
+      // the gamete matches one parental haplotype everywhere except (rare) mutations
+      const expectWholeHaplotype = (parent: Agent, gamete: number[]) => {
+        const mismatches = [0, 1].map(h =>
+          gamete.filter((x, l) => x !== parent.alleles[h * nLoci + l]).length)
+        expect(Math.min(...mismatches)).toBeLessThanOrEqual(3)
+      }
+      const rng = createRng(9)
+
+      for (let t = 0; t < 20; t++) {
+        const [child] = mate({ a: parentA, b: parentB }, params, 0, rng, 1)
+        expect(child.alleles).toHaveLength(2 * nLoci)
+        expectWholeHaplotype(parentA, child.alleles.slice(0, nLoci))
+        expectWholeHaplotype(parentB, child.alleles.slice(nLoci))
+      }
+    })
+
+    it('should mix both parental haplotypes under free recombination', () => {
+      const nLoci = 200
+      const params: Params = {
+        ...defaultParams,
+        genetics: { ...defaultParams.genetics, nLoci, recombinationRate: 0.5 }
+      }
+      const parent: Agent = {
+        id: 0,
+        alleles: [...Array(nLoci).fill(0), ...Array(nLoci).fill(1)],
+        meanAllele: 0.5, env: 0, rawenv: 0, educationScore: 0,
+        wealth: 1000, parentWealth: 0, parents: null
+      }
+      const [child] = mate({ a: parent, b: parent }, params, 0, createRng(10), 1)
+      const fromSecond = child.alleles.slice(0, nLoci).filter(x => x === 1).length
+
+      expect(fromSecond).toBeGreaterThan(0.35 * nLoci)
+      expect(fromSecond).toBeLessThan(0.65 * nLoci)
+    })
+
     it('should set parentWealth as sum of both parents wealth', () => {
       const parentA: Agent = {
         id: 0,
@@ -423,6 +1286,42 @@ describe('Model Core Functions', () => {
       })
     })
 
+    it('should divide the parents estate between the children', () => {
+      const parentA: Agent = {
+        id: 0, alleles: [0.5, 0.5], meanAllele: 0.5, env: 0.5, rawenv: 0.5,
+        educationScore: 0.5, wealth: 30000, parentWealth: 0, parents: null
+      }
+      const parentB: Agent = {
+        id: 1, alleles: [0.5, 0.5], meanAllele: 0.5, env: 0.5, rawenv: 0.5,
+        educationScore: 0.5, wealth: 70000, parentWealth: 0, parents: null
+      }
+
+      for (const inheritanceDivision of ['equal', 'primogeniture', 'weighted'] as const) {
+        const params: Params = { ...defaultParams, inheritanceDivision }
+        const children = mate({ a: parentA, b: parentB }, params, 100)
+        const inherited = children.reduce((s, c) => s + c.inheritance!, 0)
+
+        expect(inherited).toBeCloseTo(100000)
+      }
+    })
+
+    it('should produce the requested number of children', () => {
+      const parentA: Agent = {
+        id: 0, alleles: [0.5, 0.5], meanAllele: 0.5, env: 0.5, rawenv: 0.5,
+        educationScore: 0.5, wealth: 30000, parentWealth: 0, parents: null
+      }
+      const parentB: Agent = {
+        id: 1, alleles: [0.5, 0.5], meanAllele: 0.5, env: 0.5, rawenv: 0.5,
+        educationScore: 0.5, wealth: 70000, parentWealth: 0, parents: null
+      }
+
+      expect(mate({ a: parentA, b: parentB }, defaultParams, 0, undefined, 0)).toHaveLength(0)
+      const children = mate({ a: parentA, b: parentB }, defaultParams, 0, undefined, 5)
+      expect(children).toHaveLength(5)
+      expect(children.map(c => c.id)).toEqual([0, 1, 2, 3, 4])
+      expect(children.reduce((s, c) => s + c.inheritance!, 0)).toBeCloseTo(100000)
+    })
+
     it('should store parent information', () => {
       const parentA: Agent = {
         id: 42,
@@ -554,6 +1453,61 @@ describe('Model Core Functions', () => {
       })
     })
 
+    it('should accumulate wealth under the accumulation model', () => {
+      const params: Params = { ...defaultParams, populationSize: 20, accumulationModel: true }
+      const pop = initializePopulation(params)
+      const nextPop = nextGeneration(pop, params)
+
+      nextPop.forEach(agent => {
+        expect(agent.previousWealth).toBeTypeOf('number')
+        expect(agent.wealth).toBeCloseTo(agent.previousWealth! + agent.savings!)
+      })
+    })
+
+    it('should report every annual period of the generation', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 20,
+        accumulationModel: true,
+        periodsPerGeneration: 30
+      }
+      const pop = initializePopulation(params)
+      let periods = 0
+      nextGeneration(pop, params, undefined, { onPeriod: () => { periods++ } })
+
+      expect(periods).toBe(30)
+    })
+
+    it('should let the population size vary under Poisson fertility', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 200,
+        fertility: { ...defaultParams.fertility, model: 'poisson' }
+      }
+      const rng = createRng(8)
+      const sizes = new Set<number>()
+      let pop = initializePopulation(params, rng)
+      for (let g = 0; g < 5; g++) {
+        pop = nextGeneration(pop, params, rng)
+        sizes.add(pop.length)
+      }
+
+      expect(sizes.size).toBeGreaterThan(1)
+    })
+
+    it('should keep populationSize when the population is capped', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 101,
+        fertility: { ...defaultParams.fertility, model: 'poisson', meanChildren: 3, capPopulation: true }
+      }
+      let pop = initializePopulation(params)
+      for (let g = 0; g < 3; g++) {
+        pop = nextGeneration(pop, params)
+        expect(pop).toHaveLength(101)
+      }
+    })
+
     it('should handle odd population sizes by dropping one agent', () => {
       const params: Params = { ...defaultParams, populationSize: 11 }
       const pop = initializePopulation(params)
@@ -562,5 +1516,29 @@ describe('Model Core Functions', () => {
       // 11 agents -> 5 pairs -> 10 children
       expect(nextPop).toHaveLength(10)
     })
+
+    it('should go extinct without throwing when nobody has children', () => {
+      const params: Params = {
+        ...defaultParams,
+        populationSize: 20,
+        fertility: { ...defaultParams.fertility, meanChildren: 0 }
+      }
+      let pop = initializePopulation(params)
+      for (let g = 0; g < 5; g++) {
+        pop = nextGeneration(pop, params)
+        expect(pop).toHaveLength(0)
+      }
+    })
+
+    it('should advance a single agent without a marriage market', () => {
+      const params: Params = { ...defaultParams, populationSize: 1 }
+      const lone = initializePopulation(params)
+      expect(nextGeneration(lone, params)).toHaveLength(0)
+
+      const alone: Params = { ...params, singleFertility: 1 }
+      const kids = nextGeneration(lone, alone)
+      expect(kids).toHaveLength(2)
+      kids.forEach(k => expect(k.parents).toHaveLength(1))
+    })
   })
 })
@@ -1,7 +1,9 @@
 // src/model.ts
 
 import { kdTree } from 'kd-tree-javascript'
-import { normal, beta, rank, spearmancoeff } from 'jstat';
+import { normal, rank } from 'jstat';
+import { Rng, defaultRng } from './rng'
+import { mean, pearson, variance } from './metrics/stats'
 
 //for turning financialScore into wealth
 const MU_L    = Math.log(1_000);  // sets the median bulk wealth (~$50 k)
@@ -11,10 +13,18 @@ const KAPPA   = 1;               // Pareto tail exponent (larger ⇒ thinner tai
 /** Simulation parameters and defaults */
 export interface Params {
   populationSize: number
+  /** Seed for the run's random number generator (unseeded if absent) */
+  seed?: number
 
   // Education parameters
   /** Weight on genetic contribution versus environment for education */
   geneEnvWeight: number   // [0,1]
+  /** G×E: weight on polygenicScore × env in education (>0 ⇒ genes matter more in richer environments) */
+  gxeWeight: number
+  /** Standard deviation of non-shared environmental noise in education */
+  educationNoiseStd: number // ≥0
+  /** Passive rGE: shift of a child's env per unit of the parents' mean polygenic score */
+  passiveRGE: number      // ≥0
 
   // Environmental inheritance noise
   /** Standard deviation of Normal noise added to child.env */
@@ -26,12 +36,99 @@ export interface Params {
   /** Proportion of pure noise in financial outcome */
   financeNoise: number    // [0,1]
 
+  // Wealth engine
+  /** Accumulate wealth from labor + capital income instead of resampling it */
+  accumulationModel: boolean
+  /** Real return on previous wealth (accumulation model) */
+  capitalReturnRate: number // ≥0
+  /** Multiplier turning labor productivity into labor income (accumulation model) */
+  wageRate: number        // >0
+  /** Standard deviation of the annual shock to the return on capital */
+  returnVolatility: number  // ≥0
+  /** Annual periods lived by each generation (accumulation model) */
+  periodsPerGeneration: number // ≥1
+
+  // Intergenerational transfer
+  /** Share of parental wealth passed on to children */
+  inheritanceRate: number // [0,1]
+  /** Probability that a catastrophe strikes an inheritance */
+  catastropheRate: number // [0,1]
+  /** Mean share of the inheritance lost when a catastrophe strikes */
+  catastropheSeverity: number // [0,1]
+  /** Loss distribution: exactly the severity, or a retained share ~ Beta(a, 1) with mean 1 - severity */
+  catastropheDistribution: 'fixed' | 'beta'
+  /** How an estate is divided among the children born to a pair */
+  inheritanceDivision: 'equal' | 'primogeniture' | 'weighted'
+  /** Weighted division: each younger child's weight relative to the next older one */
+  inheritanceWeightDecay: number // (0,1]
+
+  // Marriage market
+  /** Share of agents who enter the marriage market; the rest stay single */
+  partnershipRate: number // [0,1]
+  /** Fertility of single agents relative to pairs (0 ⇒ singles have no children) */
+  singleFertility: number // ≥0
+  /**
+   * 'monogamous': disjoint pairs, nobody mates twice.
+   * 'sampled': legacy polygynous sampling — half the market initiates and draws
+   * a mate from the whole market, so an agent can appear in several pairs.
+   */
+  matingMode: 'monogamous' | 'sampled'
+
+  // Fertility
+  fertility: {
+    /** Children per pair: meanChildren (randomly rounded), Poisson, or overdispersed negative binomial */
+    model: 'fixed' | 'poisson' | 'negativeBinomial'
+    /** Mean number of children per pair at the median wealth and zero education */
+    meanChildren: number  // ≥0
+    /** Negative binomial shape (smaller ⇒ more variable family sizes) */
+    dispersion: number    // >0
+    /** Change in log fertility per unit of the pair's wealth percentile above the median */
+    wealthGradient: number
+    /** Change in log fertility per unit of the pair's mean educationScore */
+    educationGradient: number
+    /** Resample every new generation back to populationSize */
+    capPopulation: boolean
+  }
+  /** Fraction of total income saved, by wealth percentile tier (accumulation model) */
+  savingsRateBottom: number // [0,1]
+  savingsRateMiddle: number // [0,1]
+  savingsRateUpper: number  // [0,1]
+  savingsRateTop: number    // [0,1]
+  /** Percentile cutoffs separating the savings tiers (bottom < middle < upper) */
+  savingsCutoffBottom: number // [0,1]
+  savingsCutoffMiddle: number // [0,1]
+  savingsCutoffUpper: number  // [0,1]
+
+  // Genetic architecture of the education trait
+  genetics: {
+    /** Number of diploid loci */
+    nLoci: number           // ≥1
+    /** Additive effect of each locus (default 1/√nLoci, keeping the score's variance fixed) */
+    effectSizes?: number[]
+    /** Dominance: 0 additive, 1 ⇒ the higher allele dominates, −1 ⇒ the lower one */
+    dominance: number       // [-1,1]
+    /** Chance of a crossover between adjacent loci (0.5 ⇒ independent assortment) */
+    recombinationRate: number // [0,0.5]
+    /** Chance that each transmitted allele mutates */
+    mutationRate: number    // [0,1]
+    /** Mutation kernel: fresh N(0,1) draw, additive N(0, mutationStd) step, or no mutation */
+    mutationKernel: 'redraw' | 'gaussian' | 'none'
+    /** Standard deviation of the Gaussian mutation step */
+    mutationStd: number     // ≥0
+  }
+
   // Homophily in mate choice
   homophily: {
-    /** Strength of choosing similar meanAllele */
-    gene: number          // ≥0
-    /** Strength of choosing similar environment */
-    env: number           // ≥0
+    /** Strength of choosing similar meanAllele (negative ⇒ dissimilar) */
+    gene: number          // [-1,1]
+    /** Strength of choosing similar environment (negative ⇒ dissimilar) */
+    env: number           // [-1,1]
+    /** Strength of choosing similar educationScore (negative ⇒ dissimilar) */
+    education?: number    // [-1,1]
+    /** Strength of choosing similar log wealth (negative ⇒ dissimilar) */
+    wealth?: number       // [-1,1]
+    /** Strength of choosing similar log parental wealth (negative ⇒ dissimilar) */
+    parentWealth?: number // [-1,1]
   }
   kd?: { kNeighbors?: number}
 }
@@ -39,9 +136,50 @@ export interface Params {
 export const defaultParams: Params = {
   populationSize:  6400,
   geneEnvWeight:   0.5,
+  gxeWeight:       0,
+  educationNoiseStd: 0,
+  passiveRGE:      0,
   envNoiseStd:     0.1,
   financeWeight:   0.7,
   financeNoise:    0.1,
+  accumulationModel: false,
+  capitalReturnRate: 0.05,
+  wageRate:        1.0,
+  returnVolatility: 0,
+  periodsPerGeneration: 1,
+  inheritanceRate: 1.0,
+  // legacy behaviour: every inheritance is scaled by a Beta(0.01, 1) draw
+  catastropheRate: 1.0,
+  catastropheSeverity: 100 / 101,
+  catastropheDistribution: 'beta',
+  inheritanceDivision: 'equal',
+  inheritanceWeightDecay: 0.5,
+  partnershipRate: 1.0,
+  singleFertility: 0,
+  matingMode: 'monogamous',
+  fertility: {
+    model: 'fixed',
+    meanChildren: 2,
+    dispersion: 2,
+    wealthGradient: 0,
+    educationGradient: 0,
+    capPopulation: false,
+  },
+  savingsRateBottom: 0.05,
+  savingsRateMiddle: 0.15,
+  savingsRateUpper:  0.40,
+  savingsRateTop:    0.70,
+  savingsCutoffBottom: 0.20,
+  savingsCutoffMiddle: 0.80,
+  savingsCutoffUpper:  0.99,
+  genetics: {
+    nLoci: 1,
+    dominance: 0,
+    recombinationRate: 0.5,
+    mutationRate: 0.01,
+    mutationKernel: 'redraw',
+    mutationStd: 0.1,
+  },
   homophily: {
     gene: 0.0,
     env:  0.0,
@@ -51,53 +189,106 @@ export const defaultParams: Params = {
 /** One individual in the simulation */
 export interface Agent {
   id: number
-  alleles: [number, number]     // two real-valued genes
+  alleles: number[]             // two haplotypes of nLoci real-valued genes, back to back
   meanAllele: number          // mean of alleles (for convenience)
-  parentWealth: number
+  polygenicScore?: number     // genetic value entering educationScore (see computePolygenicScore)
+  mutations?: number          // mutated alleles among those inherited from the parents
+  parentWealth: number          // combined wealth of both parents
+  inheritance?: number          // this child's share of the parents' estate
   env: number                   // scalar environment
   rawenv: number // without noise
   educationScore: number
   wealth: number //computed from financialScore
-  parents: [Agent, Agent] | null
+  parents: [Agent, Agent] | [Agent] | null   // one parent for children of singles
+  catastrophe?: boolean       // whether a catastrophe struck this agent's inheritance
+  // accumulation model bookkeeping for the latest period (undefined under the sampling model)
+  laborIncome?: number
+  capitalIncome?: number
+  savings?: number
+  previousWealth?: number     // wealth before this period's savings
 }
 
 /**
  * Bootstrap the initial population.
- * - alleles ∼ N(0,1)
+ * - alleles ∼ N(0,1), 2 × nLoci per agent
  * - env ∼ N(0,1)
  * - initial scores computed (parent wealth = 0)
  */
-export function initializePopulation(params: Params): Agent[] {
+export function initializePopulation(
+  params: Params,
+  rng: Rng = defaultRng
+): Agent[] {
   const pop: Agent[] = []
   
   for (let i = 0; i < params.populationSize; i++) {
-    const alleles = [normal.sample(0, 1), normal.sample(0, 1)] as [number, number]
-    const env = normal.sample(0, 1) // N(0,1) environment
+    const alleles = Array.from({ length: 2 * params.genetics.nLoci }, () => rng.normal(0, 1))
+    const env = rng.normal(0, 1) // N(0,1) environment
     const a: Agent = {
       id: i,
       alleles,
-      meanAllele: (alleles[0] + alleles[1]) / 2,
+      meanAllele: mean(alleles),
+      polygenicScore: computePolygenicScore(alleles, params),
       parentWealth: 100_000, // no parents yet
       env: env,
       rawenv: env, // without noise
-      educationScore: normal.sample(0, 1),
+      educationScore: rng.normal(0, 1),
       parents: null,
       wealth: 0,
     }
-    a.wealth = computeWealthFromScore(a, params)
     pop.push(a)
   }
+  computePopulationWealth(pop, params, rng)
 
   return pop
 }
 
-/** Education score = weighted sum of mean allele and environment */
-export function computeEducationScore(a: Agent, params: Params): number {
-  return params.geneEnvWeight * a.meanAllele
+/**
+ * Genetic value of an allele vector (haplotype 0 then haplotype 1, nLoci each).
+ * Each locus contributes effect × (mean of its two alleles + dominance × half their gap),
+ * so dominance 1 counts only the higher allele and −1 only the lower one.
+ * With one locus, unit effect and no dominance this is just the mean allele.
+ */
+export function computePolygenicScore(alleles: number[], params: Params): number {
+  const { effectSizes, dominance } = params.genetics
+  const n = alleles.length / 2
+  let score = 0
+  for (let l = 0; l < n; l++) {
+    const x = alleles[l]
+    const y = alleles[n + l]
+    const effect = effectSizes?.[l] ?? 1 / Math.sqrt(n)
+    score += effect * ((x + y) / 2 + dominance * Math.abs(x - y) / 2)
+  }
+  return score
+}
+
+/**
+ * Education score = weighted sum of polygenic score and environment,
+ * plus an optional G×E interaction and non-shared environmental noise
+ */
+export function computeEducationScore(
+  a: Agent,
+  params: Params,
+  rng: Rng = defaultRng
+): number {
+  const g = a.polygenicScore ?? a.meanAllele
+  const noise = params.educationNoiseStd > 0 ? rng.normal(0, params.educationNoiseStd) : 0
+  return params.geneEnvWeight * g
        + (1 - params.geneEnvWeight) * a.env
+       + params.gxeWeight * g * a.env
+       + noise
+}
+
+/** Passive rGE: the environment parents provide shifts with their own genes */
+function parentalGeneticShift(a: Agent, params: Params): number {
+  if (params.passiveRGE === 0 || !a.parents) return 0
+  return params.passiveRGE * mean(a.parents.map(p => p.polygenicScore ?? p.meanAllele))
 }
 
-export function envFromWealth(agents: Agent[], params: Params): void {
+export function envFromWealth(
+  agents: Agent[],
+  params: Params,
+  rng: Rng = defaultRng
+): void {
   const inc = agents.map(a => a.parentWealth);
 
   // 1) Compute average ranks (ties are averaged)
@@ -111,15 +302,19 @@ export function envFromWealth(agents: Agent[], params: Params): void {
   agents.forEach((agent, i) => {
     const p = Math.max(rawRanks[i] / denom, minP);
     // 3) Inverse-CDF of standard normal
-    agent.rawenv = normal.inv(p, 0, 1);
-    agent.env = agent.rawenv + normal.sample(0, params.envNoiseStd); 
+    agent.rawenv = normal.inv(p, 0, 1) + parentalGeneticShift(agent, params);
+    agent.env = agent.rawenv + rng.normal(0, params.envNoiseStd);
   });
 }
 
 // --- FUNCTION: map a single financialScore to an wealth ---
-export function computeWealthFromScore(a: Agent, params: Params): number {
+export function computeWealthFromScore(
+  a: Agent,
+  params: Params,
+  rng: Rng = defaultRng
+): number {
   function computePotentialWealth(score: number, noise: number): number {
-    const noiseTerm = noise === 0 ? 0 : normal.sample(0, noise)
+    const noiseTerm = noise === 0 ? 0 : rng.normal(0, noise)
     const L = Math.exp(
       MU_L
       + SIGMA_L * score
@@ -136,25 +331,194 @@ export function computeWealthFromScore(a: Agent, params: Params): number {
   }
 
   const potentialWealth = computePotentialWealth(a.educationScore, params.financeNoise);
-  // 4. apply finance weight and small chance of parent wealth catastrophe
+  // 4. apply finance weight to the environment component plus inheritance
   const wealth = params.financeWeight * potentialWealth
     + (1 - params.financeWeight) 
-    * (computePotentialWealth(a.rawenv, 0) + a.parentWealth * beta.sample(.01, 1));
+    * (computePotentialWealth(a.rawenv, 0) + inheritWealth(a, params, rng));
 
   return wealth;
 }
 
+/** Labor income = wageRate × exp(μ_L + σ_L × educationScore + noise) */
+export function computeLaborIncome(
+  a: Agent,
+  params: Params,
+  rng: Rng = defaultRng
+): number {
+  const noiseTerm = params.financeNoise === 0 ? 0 : rng.normal(0, params.financeNoise)
+  return params.wageRate * Math.exp(MU_L + SIGMA_L * a.educationScore + noiseTerm)
+}
+
+/**
+ * Percentile of `value` within ascending `sorted`: the share of values
+ * strictly below it, found by binary search.
+ */
+export function computePercentile(value: number, sorted: number[]): number {
+  let lo = 0
+  let hi = sorted.length
+  while (lo < hi) {
+    const mid = (lo + hi) >>> 1
+    if (sorted[mid] < value) lo = mid + 1
+    else hi = mid
+  }
+  return sorted.length === 0 ? 0 : lo / sorted.length
+}
+
+/** Percentiles of every value (see computePercentile) from a single sort */
+export function computePercentiles(values: number[]): number[] {
+  const N = values.length
+  const order = values.map((_, i) => i).sort((i, j) => values[i] - values[j])
+  const percentiles = new Array<number>(N)
+  let tieStart = 0
+  order.forEach((idx, pos) => {
+    if (pos > 0 && values[idx] !== values[order[pos - 1]]) tieStart = pos
+    percentiles[idx] = tieStart / N
+  })
+  return percentiles
+}
+
+/** Savings rate of the tier that a wealth percentile falls into */
+export function getSavingsRate(percentile: number, params: Params): number {
+  if (percentile < params.savingsCutoffBottom) return params.savingsRateBottom
+  if (percentile < params.savingsCutoffMiddle) return params.savingsRateMiddle
+  if (percentile < params.savingsCutoffUpper)  return params.savingsRateUpper
+  return params.savingsRateTop
+}
+
+/** Share of an inheritance lost when a catastrophe strikes */
+export function drawCatastropheLoss(
+  params: Params,
+  rng: Rng = defaultRng
+): number {
+  const severity = params.catastropheSeverity
+  if (params.catastropheDistribution === 'fixed') return severity
+  if (severity <= 0) return 0
+  if (severity >= 1) return 1
+  // retained share ~ Beta((1 - s) / s, 1), whose mean is 1 - s
+  return 1 - rng.beta((1 - severity) / severity, 1)
+}
+
+/**
+ * Inherited starting wealth: inheritanceRate × parent wealth, less any
+ * catastrophe loss. Records on the agent whether a catastrophe struck.
+ */
+export function inheritWealth(
+  a: Agent,
+  params: Params,
+  rng: Rng = defaultRng
+): number {
+  a.catastrophe = rng.random() < params.catastropheRate
+  const loss = a.catastrophe ? drawCatastropheLoss(params, rng) : 0
+  const estateShare = a.inheritance ?? a.parentWealth
+  return estateShare * params.inheritanceRate * (1 - loss)
+}
+
+/** Fraction of agents whose inheritance was struck by a catastrophe */
+export function catastropheFraction(pop: Agent[]): number {
+  if (pop.length === 0) return 0
+  return pop.filter(a => a.catastrophe).length / pop.length
+}
+
+// --- FUNCTION: accumulate one period of labor and capital income ---
+/**
+ * Accumulation engine (docs/NEW_ECONOMIC_MODEL.md), one annual period:
+ * - capitalIncome  = (capitalReturnRate + shock) × previousWealth
+ * - savings        = savingsRate × (laborIncome + capitalIncome)
+ * - wealth         = previousWealth + savings
+ * Negative total income (a capital loss) is not smoothed by consumption and
+ * hits wealth in full. Records the components on the agent and returns the
+ * new wealth.
+ */
+export function computeWealthFromAccumulation(
+  a: Agent,
+  params: Params,
+  savingsRate: number,
+  rng: Rng = defaultRng
+): number {
+  const previousWealth = a.previousWealth ?? 0
+  const laborIncome = computeLaborIncome(a, params, rng)
+  const returnShock = params.returnVolatility === 0 ? 0 : rng.normal(0, params.returnVolatility)
+  const capitalIncome = (params.capitalReturnRate + returnShock) * previousWealth
+  const totalIncome = laborIncome + capitalIncome
+  const savings = totalIncome > 0 ? savingsRate * totalIncome : totalIncome
+
+  a.previousWealth = previousWealth
+  a.laborIncome = laborIncome
+  a.capitalIncome = capitalIncome
+  a.savings = savings
+
+  return previousWealth + savings
+}
+
+/** Called after each annual period with the population and the period (1-based) */
+export type PeriodCallback = (pop: Agent[], period: number) => void
+
+/**
+ * Set every agent's wealth with the engine selected by params.accumulationModel.
+ * - sampling: one draw per generation (a single period)
+ * - accumulation: inherit, then compound periodsPerGeneration annual periods,
+ *   ranking wealth once per period for the savings tiers
+ */
+export function computePopulationWealth(
+  pop: Agent[],
+  params: Params,
+  rng: Rng = defaultRng,
+  onPeriod?: PeriodCallback
+): void {
+  if (!params.accumulationModel) {
+    pop.forEach(a => { a.wealth = computeWealthFromScore(a, params, rng) })
+    onPeriod?.(pop, 1)
+    return
+  }
+  pop.forEach(a => { a.wealth = inheritWealth(a, params, rng) })
+  const periods = Math.max(1, Math.round(params.periodsPerGeneration))
+  for (let t = 1; t <= periods; t++) {
+    pop.forEach(a => { a.previousWealth = a.wealth })
+    const percentiles = computePercentiles(pop.map(a => a.wealth))
+    pop.forEach((a, i) => {
+      const rate = getSavingsRate(percentiles[i], params)
+      a.wealth = computeWealthFromAccumulation(a, params, rate, rng)
+    })
+    onPeriod?.(pop, t)
+  }
+}
+
+/** Agent traits that mate choice can be assortative on, keyed as in params.homophily */
+export type MatingTrait = 'gene' | 'env' | 'education' | 'wealth' | 'parentWealth'
+
+export const MATING_TRAITS: MatingTrait[] = ['gene', 'env', 'education', 'wealth', 'parentWealth']
+
+const traitValue: Record<MatingTrait, (a: Agent) => number> = {
+  gene:         a => a.polygenicScore ?? a.meanAllele,
+  env:          a => a.env,
+  education:    a => a.educationScore,
+  wealth:       a => Math.log1p(Math.max(0, a.wealth)),
+  parentWealth: a => Math.log1p(Math.max(0, a.parentWealth)),
+}
+
+/** Traits rescaled to unit standard deviation before distances are taken */
+const STANDARDIZED_TRAITS: MatingTrait[] = ['wealth', 'parentWealth']
+
 /** Helper type for a mating pair */
-interface Pair { a: Agent; b: Agent }
+export interface Pair { a: Agent; b: Agent }
 
 /**
- * Select disjoint mating pairs (no agent repeats) with homophily on genes/env.
- * - Shuffle pool
- * - For each agent A in turn, choose B from remaining by weighted similarity
+ * Select disjoint mating pairs (no agent repeats) with homophily on genes, env,
+ * education and (log) wealth — every trait with a non-zero weight in params.homophily.
+ * - Each agent enters the marriage market with probability partnershipRate
+ * - Shuffle the market
+ * - For each unmatched agent A in turn, choose B from the unmatched by weighted similarity
+ * Agents left over stay single.
+ *
+ * Negative homophily makes mating disassortative: dissimilar partners are preferred.
+ *
+ * With matingMode 'sampled' the pairs are not disjoint: the first half of the
+ * shuffled market each draw a mate (other than themselves) from the whole market.
  */
 export function selectMatingPool(
   pop: Agent[],
-  params: Params
+  params: Params,
+  rng: Rng = defaultRng
 ): Pair[] {
   const N = pop.length
 
@@ -168,52 +532,142 @@ export function selectMatingPool(
   const K = kd?.kNeighbors ?? 10
   
   const MAX_ALPHA = 125
-    // compute stretch factors
-  const alphag = homophily.gene === 1
-    ? MAX_ALPHA
-    : homophily.gene / (1 - homophily.gene);
-  const alphae = homophily.env  === 1
-    ? MAX_ALPHA
-    : homophily.env  / (1 - homophily.env);
-
-  type KDNode = { meanAllele: number, env: number, idx: number }
-  const nodes: KDNode[] = pop.map((a, i) => ({
-    meanAllele: a.meanAllele,
-    env:    a.env,
-    idx:    i
-  }))
+    // compute stretch factors from |homophily|; the sign picks like (+) or unlike (−) partners
+  const stretch = (h: number) => {
+    const m = Math.min(Math.abs(h), 1)
+    return m === 1 ? MAX_ALPHA : m / (1 - m)
+  }
+
+  // the similarity space: every trait with a non-zero homophily weight
+  const weights = MATING_TRAITS.map(t => homophily[t] ?? 0)
+  const active  = MATING_TRAITS.filter((_, j) => weights[j] !== 0)
+  const alphas  = active.map(t => stretch(homophily[t]!))
+  const signs   = active.map(t => Math.sign(homophily[t]!))
+  const disassortative = signs.some(sg => sg < 0)
+
+  type KDNode = Record<string, number> & { idx: number }
+  const nodes: KDNode[] = pop.map((a, i) => ({ idx: i } as KDNode))
+  active.forEach(t => {
+    const xs = pop.map(traitValue[t])
+    // log-wealth traits are standardized so their weights compare with the unit-scale ones
+    const sd = STANDARDIZED_TRAITS.includes(t) ? Math.sqrt(variance(xs)) || 1 : 1
+    nodes.forEach((n, i) => { n[t] = xs[i] / sd })
+  })
+
+  const dist = (u: KDNode, v: KDNode) => {
+    let d = 0
+    for (let j = 0; j < active.length; j++) d += alphas[j] * Math.abs(u[active[j]] - v[active[j]])
+    return d
+  }
+
+  // signed distance: lower is more attractive, so negative homophily rewards being far apart
+  const signedDist = (u: KDNode, v: KDNode) => {
+    let d = 0
+    for (let j = 0; j < active.length; j++) d += signs[j] * alphas[j] * Math.abs(u[active[j]] - v[active[j]])
+    return d
+  }
 
-  const dist = (u: KDNode, v: KDNode) =>
-    alphag * Math.abs(u.meanAllele - v.meanAllele)
-  + alphae * Math.abs(u.env        - v.env)
+  // marriage market: each agent enters with probability partnershipRate
+  const participants = params.partnershipRate >= 1
+    ? nodes
+    : nodes.filter(() => rng.random() < params.partnershipRate)
 
-  const tree = new kdTree(nodes, dist, ['meanAllele','env'])
+  // the kd tree only answers nearest-neighbour queries; disassortative mating scans instead
+  const tree = disassortative || active.length === 0
+    ? null
+    : new kdTree(participants, dist, active)
+  const participantIdx = participants.map(n => n.idx)
+
+  // the k candidates with the lowest signed distance to aNode, by linear scan;
+  // with every homophily weight negative these are its k farthest neighbours
+  function farthest(aNode: KDNode, k: number, pool: number[]): [KDNode, number][] {
+    const best: [KDNode, number][] = []
+    for (const idx of pool) {
+      if (idx === aNode.idx) continue
+      const d = signedDist(aNode, nodes[idx])
+      if (best.length === k && d >= best[k - 1][1]) continue
+      let j = Math.min(best.length, k - 1)
+      while (j > 0 && best[j - 1][1] > d) {
+        best[j] = best[j - 1]
+        j--
+      }
+      best[j] = [nodes[idx], d]
+    }
+    return best
+  }
 
   function shuffle<T>(arr: T[]): T[] {
     const a = arr.slice()    // copy
     for (let i = a.length - 1; i > 0; i--) {
-      const j = Math.floor(Math.random() * (i + 1))
+      const j = rng.int(i + 1)
       ;[a[i], a[j]] = [a[j], a[i]]
     }
     return a
   }
 
-  const nPairs     = Math.floor(N / 2)
-  const initiators = shuffle(nodes).slice(0, nPairs)
-  
+  // unmatched participants, kept in an array with swap-removal for O(1) random picks
+  const matched   = new Array<boolean>(N).fill(false)
+  const available = participants.map(n => n.idx)
+  const slot      = new Array<number>(N).fill(-1)
+  available.forEach((idx, s) => { slot[idx] = s })
+  function take(idx: number) {
+    matched[idx] = true
+    const s = slot[idx]
+    const last = available.pop()!
+    if (last !== idx) {
+      available[s] = last
+      slot[last] = s
+    }
+  }
+
+  const sampled = params.matingMode === 'sampled'
+  const initiators = sampled
+    ? shuffle(participants).slice(0, Math.floor(participants.length / 2))
+    : shuffle(participants)
+
+  // sampled mode: any participant other than the initiator
+  function randomOther(idx: number): number {
+    let other = idx
+    while (other === idx) other = participants[rng.int(participants.length)].idx
+    return other
+  }
+
   const pairs: Pair[] = []
-  const avgHomophily = (homophily.gene + homophily.env) / 2
+  // chance of choosing by similarity: mean |weight| over the traits with a non-zero weight
+  const avgHomophily = active.length === 0
+    ? 0
+    : weights.reduce((s, w) => s + Math.abs(w), 0) / active.length
   for (const aNode of initiators){
+    if (!sampled) {
+      if (matched[aNode.idx]) continue
+      if (available.length < 2) break
+      take(aNode.idx)
+    }
+
     // initialize bNodeIdx
     let bNodeIdx = -1
     //compute random number between 0 and 1, if it is greater than avgHomophily, randomly select a bNode
-    if (Math.random() > avgHomophily) {
-      //randomly select an index for bNode
-      bNodeIdx = Math.floor(Math.random() * N)
+    if (rng.random() > avgHomophily) {
+      //randomly select an index for bNode among the unmatched
+      bNodeIdx = sampled
+        ? randomOther(aNode.idx)
+        : available[rng.int(available.length)]
     } else {
-      let neighsWithDist = tree
-        .nearest(aNode, K + 1)
-        .filter(([n]) => n.idx !== aNode.idx)
+      let neighsWithDist: [KDNode, number][] = []
+      if (!tree) {
+        neighsWithDist = farthest(aNode, K, sampled ? participantIdx : available)
+      } else {
+        // widen the search until some of the nearest neighbours are still unmatched
+        let k = K
+        for (;;) {
+          neighsWithDist = tree
+            .nearest(aNode, k + 1)
+            .filter(([n]: [KDNode, number]) =>
+              sampled ? n.idx !== aNode.idx : !matched[n.idx])
+          if (neighsWithDist.length > 0 || k + 1 >= participants.length) break
+          k *= 2
+        }
+      }
 
       if (neighsWithDist.length === 0) {
         throw new Error(
@@ -238,7 +692,7 @@ export function selectMatingPool(
       const weights = clamped.map(sd => Math.exp(-sd))
       
       const totalW = weights.reduce((s,w) => s + w, 0)
-      let r = Math.random() * totalW
+      let r = rng.random() * totalW
       let i = 0
       while (r > weights[i]) {
         r -= weights[i++]
@@ -247,6 +701,7 @@ export function selectMatingPool(
       const bNode = neighsWithDist[i][0]
       bNodeIdx = bNode.idx
     }
+    if (!sampled) take(bNodeIdx)
     pairs.push({
       a: pop[aNode.idx],
       b: pop[bNodeIdx]
@@ -255,58 +710,228 @@ export function selectMatingPool(
   return pairs
 }
 
+/** Agents who did not end up in any pair */
+export function findSingles(pop: Agent[], pairs: Pair[]): Agent[] {
+  const partnered = new Set<Agent>()
+  pairs.forEach(p => { partnered.add(p.a); partnered.add(p.b) })
+  return pop.filter(a => !partnered.has(a))
+}
+
+/** Summary of one generation's marriage market */
+export interface PairingStats {
+  /** Share of agents in a pair */
+  fractionPartnered: number
+  /** Spousal Pearson correlations (wealth on a log scale) */
+  spousalCorrelation: { wealth: number, meanAllele: number, env: number, education: number }
+}
+
 /**
- * Given a mating pair, produce two children:
- * - Each child picks one allele from each parent
- * - env = parentAvgFin + N(0, envNoiseStd)
- * - Scores computed immediately
+ * Fraction partnered and spousal correlations. Each pair enters both ways
+ * round (double entry), so the correlation does not depend on who initiated.
+ */
+export function computePairingStats(pop: Agent[], pairs: Pair[]): PairingStats {
+  const partnered = new Set<Agent>()
+  pairs.forEach(p => { partnered.add(p.a); partnered.add(p.b) })
+
+  const spouses = [...pairs.map(p => [p.a, p.b]), ...pairs.map(p => [p.b, p.a])]
+  const corr = (f: (a: Agent) => number) =>
+    pearson(spouses.map(([x]) => f(x)), spouses.map(([, y]) => f(y)))
+  const logWealth = (a: Agent) => Math.log1p(Math.max(0, a.wealth))
+
+  return {
+    fractionPartnered: pop.length === 0 ? 0 : partnered.size / pop.length,
+    spousalCorrelation: {
+      wealth:     corr(logWealth),
+      meanAllele: corr(a => a.meanAllele),
+      env:        corr(a => a.env),
+      education:  corr(a => a.educationScore),
+    },
+  }
+}
+
+/**
+ * Divide an estate among n children (eldest first) by params.inheritanceDivision:
+ * - equal: estate / n each
+ * - primogeniture: everything to the eldest
+ * - weighted: shares proportional to inheritanceWeightDecay^birthOrder
+ * Shares always sum to the estate.
+ */
+export function divideEstate(
+  estate: number,
+  nChildren: number,
+  params: Params
+): number[] {
+  if (nChildren <= 0) return []
+  let weights: number[]
+  switch (params.inheritanceDivision) {
+    case 'primogeniture':
+      weights = Array.from({ length: nChildren }, (_, k) => k === 0 ? 1 : 0)
+      break
+    case 'weighted':
+      weights = Array.from({ length: nChildren }, (_, k) => Math.pow(params.inheritanceWeightDecay, k))
+      break
+    default:
+      weights = Array.from({ length: nChildren }, () => 1)
+  }
+  const totalW = weights.reduce((s, w) => s + w, 0)
+  return weights.map(w => estate * w / totalW)
+}
+
+/**
+ * Expected number of children for a pair:
+ * meanChildren × exp(wealthGradient × (wealthPercentile - 0.5) + educationGradient × education)
+ */
+export function fertilityMean(
+  wealthPercentile: number,
+  education: number,
+  params: Params
+): number {
+  const { meanChildren, wealthGradient, educationGradient } = params.fertility
+  return meanChildren * Math.exp(
+    wealthGradient * (wealthPercentile - 0.5)
+    + educationGradient * education
+  )
+}
+
+/** Draw a number of children with the given mean from params.fertility.model */
+export function drawNumberOfChildren(
+  mean: number,
+  params: Params,
+  rng: Rng = defaultRng
+): number {
+  switch (params.fertility.model) {
+    case 'poisson':
+      return rng.poisson(mean)
+    case 'negativeBinomial': {
+      // Gamma–Poisson mixture with shape `dispersion` and mean `mean`
+      const k = params.fertility.dispersion
+      return mean <= 0 ? 0 : rng.poisson(rng.gamma(k, mean / k))
+    }
+    default: {
+      // random rounding keeps the mean exact: 2 ⇒ always 2, 2.3 ⇒ 2 or 3
+      const whole = Math.floor(mean)
+      const frac = mean - whole
+      return frac > 0 && rng.random() < frac ? whole + 1 : whole
+    }
+  }
+}
+
+/**
+ * Resample a population to exactly `size` agents: a uniform subset without
+ * replacement when too large, or copies of random agents when too small.
+ * Estates are conserved: a copied agent's inheritance is split evenly between
+ * it and its copies, and the inheritance of dropped agents goes to the
+ * survivors in proportion to their own (evenly if they have none).
+ * Ids are renumbered 0..size-1.
+ */
+export function resamplePopulation(
+  pop: Agent[],
+  size: number,
+  rng: Rng = defaultRng
+): Agent[] {
+  if (pop.length === 0 || pop.length === size) return pop
+
+  let out: Agent[]
+  if (pop.length > size) {
+    // partial Fisher–Yates shuffle
+    out = pop.slice()
+    for (let i = 0; i < size; i++) {
+      const j = i + rng.int(out.length - i)
+      ;[out[i], out[j]] = [out[j], out[i]]
+    }
+    const dropped = out.slice(size).reduce((s, a) => s + (a.inheritance ?? 0), 0)
+    out = out.slice(0, size)
+    const kept = out.reduce((s, a) => s + (a.inheritance ?? 0), 0)
+    if (dropped !== 0) {
+      out.forEach(a => {
+        a.inheritance = kept !== 0
+          ? (a.inheritance ?? 0) * (1 + dropped / kept)
+          : (a.inheritance ?? 0) + dropped / size
+      })
+    }
+  } else {
+    const copies: Agent[][] = pop.map(a => [a])
+    out = pop.slice()
+    while (out.length < size) {
+      const i = rng.int(pop.length)
+      const copy = { ...pop[i] }
+      copies[i].push(copy)
+      out.push(copy)
+    }
+    copies.forEach(group => {
+      const estate = group[0].inheritance
+      if (estate === undefined || group.length === 1) return
+      group.forEach(a => { a.inheritance = estate / group.length })
+    })
+  }
+  out.forEach((a, i) => { a.id = i })
+  return out
+}
+
+/**
+ * Given a mating pair, produce nChildren children (two by default):
+ * - Each child gets one recombined haplotype (gamete) from each parent
+ * - The parents' combined wealth is divided among the children as inheritance
+ * - env and scores are computed later, in nextGeneration
  */
 export function mate(
   pair: Pair,
   params: Params,
-  nextIdStart: number
+  nextIdStart: number,
+  rng: Rng = defaultRng,
+  nChildren = 2
 ): Agent[] {
   const parentWealth = (pair.a.wealth + pair.b.wealth)
+  const shares = divideEstate(parentWealth, nChildren, params)
   const kids: Agent[] = []
 
-  for (let k = 0; k < 2; k++) {
+  for (let k = 0; k < nChildren; k++) {
+    //draw new haplotypes with a small chance of mutation
+    const fromA = gamete(pair.a, params, rng)
+    const fromB = gamete(pair.b, params, rng)
+    const child: Agent = {
+      id: nextIdStart + k,
+      alleles: [...fromA.alleles, ...fromB.alleles],
+      mutations: fromA.mutations + fromB.mutations,
+      parentWealth: parentWealth,
+      inheritance: shares[k],
+      educationScore: 0,
+      parents: [parentSnapshot(pair.a), parentSnapshot(pair.b)]
+    }
+    kids.push(child)
+  }
+
+  return kids
+}
+
+/**
+ * Children of an unpartnered agent:
+ * - One haplotype from the parent; the other from an unobserved partner, ~ N(0,1)
+ * - The parent's own wealth is divided among the children as inheritance
+ */
+export function reproduceAlone(
+  parent: Agent,
+  params: Params,
+  nextIdStart: number,
+  rng: Rng = defaultRng,
+  nChildren = 1
+): Agent[] {
+  const shares = divideEstate(parent.wealth, nChildren, params)
+  const kids: Agent[] = []
+
+  for (let k = 0; k < nChildren; k++) {
+    const fromParent = gamete(parent, params, rng)
     const child: Agent = {
       id: nextIdStart + k,
-      //draw new alleles with a small chance of mutation
       alleles: [
-        Math.random() < .01 ? 
-          normal.sample(0, 1) :
-          pair.a.alleles[Math.floor(Math.random() * 2)],
-        Math.random() < .01 ? 
-          normal.sample(0, 1) :
-          pair.b.alleles[Math.floor(Math.random() * 2)],
+        ...fromParent.alleles,
+        ...Array.from({ length: parent.alleles.length / 2 }, () => rng.normal(0, 1))
       ],
-      parentWealth: parentWealth,
+      mutations: fromParent.mutations,
+      parentWealth: parent.wealth,
+      inheritance: shares[k],
       educationScore: 0,
-      parents: [
-        {
-          id: pair.a.id,
-          alleles: pair.a.alleles,
-          meanAllele: pair.a.meanAllele,
-          parentWealth: pair.a.parentWealth,
-          env: pair.a.env,
-          rawenv: pair.a.rawenv,
-          educationScore: pair.a.educationScore,
-          wealth: pair.a.wealth,
-          parents: null
-        },
-        {
-          id: pair.b.id,
-          alleles: pair.b.alleles,
-          meanAllele: pair.b.meanAllele,
-          parentWealth: pair.b.parentWealth,
-          env: pair.b.env,
-          rawenv: pair.b.rawenv,
-          educationScore: pair.b.educationScore,
-          wealth: pair.b.wealth,
-          parents: null
-        }
-      ]
+      parents: [parentSnapshot(parent)]
     }
     kids.push(child)
   }
@@ -314,35 +939,142 @@ export function mate(
   return kids
 }
 
+/** Apply the mutation kernel to one allele */
+export function mutateAllele(allele: number, params: Params, rng: Rng = defaultRng): number {
+  switch (params.genetics.mutationKernel) {
+    case 'gaussian':
+      return allele + rng.normal(0, params.genetics.mutationStd)
+    case 'none':
+      return allele
+    default:
+      return rng.normal(0, 1)
+  }
+}
+
+/**
+ * One haplotype passed on by a parent (Mendelian segregation with recombination):
+ * start on a random parental haplotype and switch at each locus boundary with
+ * probability recombinationRate. Each allele mutates with probability mutationRate.
+ */
+function gamete(
+  parent: Agent,
+  params: Params,
+  rng: Rng
+): { alleles: number[], mutations: number } {
+  const { recombinationRate, mutationRate, mutationKernel } = params.genetics
+  const n = parent.alleles.length / 2
+  const alleles = new Array<number>(n)
+  let mutations = 0
+  let h = rng.int(2)
+  for (let l = 0; l < n; l++) {
+    if (l > 0 && rng.random() < recombinationRate) h = 1 - h
+    const inherited = parent.alleles[h * n + l]
+    if (mutationKernel !== 'none' && rng.random() < mutationRate) {
+      alleles[l] = mutateAllele(inherited, params, rng)
+      mutations++
+    } else {
+      alleles[l] = inherited
+    }
+  }
+  return { alleles, mutations }
+}
+
+/** Total mutations carried by a generation (see Agent.mutations) */
+export function mutationCount(pop: Agent[]): number {
+  return pop.reduce((s, a) => s + (a.mutations ?? 0), 0)
+}
+
+/** Copy of a parent's traits without their own ancestry */
+function parentSnapshot(a: Agent): Agent {
+  return {
+    id: a.id,
+    alleles: a.alleles,
+    meanAllele: a.meanAllele,
+    polygenicScore: a.polygenicScore,
+    parentWealth: a.parentWealth,
+    env: a.env,
+    rawenv: a.rawenv,
+    educationScore: a.educationScore,
+    wealth: a.wealth,
+    parents: null
+  }
+}
+
+/** Optional observers of the steps inside nextGeneration */
+export interface GenerationHooks {
+  /** Called after every annual period */
+  onPeriod?: PeriodCallback
+  /** Called once the marriage market has formed its pairs */
+  onPairing?: (pairs: Pair[], pop: Agent[]) => void
+}
+
 /**
  * Advance one generation:
- * 1. Select monogamous mating pairs
- * 2. Mate each pair to get two children
- * 3. Return new population of size ~N (drops one if N odd)
+ * 1. Select monogamous mating pairs; the rest stay single
+ * 2. Mate each pair; family size comes from the fertility model
+ *    (by default exactly two children, so N stays ~N and drops one if N odd).
+ *    Singles have singleFertility times the children of a pair.
+ * 3. Optionally resample the children back to populationSize
+ * 4. Children inherit, then live out the generation's periods
+ *
+ * A population of fewer than two has no marriage market; unless a lone agent
+ * reproduces alone, the next generation is empty. Callers should check for an
+ * empty (extinct) population rather than advance it further.
  */
 export function nextGeneration(
   oldPop: Agent[],
-  params: Params
+  params: Params,
+  rng: Rng = defaultRng,
+  hooks: GenerationHooks = {}
 ): Agent[] {
-  const pairs = selectMatingPool(oldPop, params)
-  const newPop: Agent[] = []
+  const pairs = oldPop.length < 2 ? [] : selectMatingPool(oldPop, params, rng)
+  const singles = findSingles(oldPop, pairs)
+  hooks.onPairing?.(pairs, oldPop)
+
+  // rank every household (pair or single) by wealth for the fertility gradient
+  const householdPercentiles = computePercentiles([
+    ...pairs.map(p => p.a.wealth + p.b.wealth),
+    ...singles.map(a => a.wealth),
+  ])
+  let newPop: Agent[] = []
   let nextId = 0
-  pairs.forEach(pair => {
-    const kids = mate(pair, params, nextId)
+  pairs.forEach((pair, i) => {
+    const mean = fertilityMean(
+      householdPercentiles[i],
+      (pair.a.educationScore + pair.b.educationScore) / 2,
+      params
+    )
+    const nChildren = drawNumberOfChildren(mean, params, rng)
+    const kids = mate(pair, params, nextId, rng, nChildren)
     kids.forEach(k => {
       newPop.push(k)
       nextId++
     })
   })
-  envFromWealth(newPop, params);
+  if (params.singleFertility > 0) {
+    singles.forEach((single, j) => {
+      const mean = params.singleFertility
+        * fertilityMean(householdPercentiles[pairs.length + j], single.educationScore, params)
+      const nChildren = drawNumberOfChildren(mean, params, rng)
+      const kids = reproduceAlone(single, params, nextId, rng, nChildren)
+      kids.forEach(k => {
+        newPop.push(k)
+        nextId++
+      })
+    })
+  }
+  if (params.fertility.capPopulation) {
+    newPop = resamplePopulation(newPop, params.populationSize, rng)
+  }
+  envFromWealth(newPop, params, rng);
   newPop.forEach(kid => {
-    const [n0, n1] = kid.alleles;
-    kid.meanAllele = (n0 + n1) / 2;
+    kid.meanAllele = mean(kid.alleles);
+    kid.polygenicScore = computePolygenicScore(kid.alleles, params);
   })
   newPop.forEach(kid => {
-    kid.educationScore = computeEducationScore(kid, params);
-    kid.wealth = computeWealthFromScore(kid, params);
+    kid.educationScore = computeEducationScore(kid, params, rng);
   })
+  computePopulationWealth(newPop, params, rng, hooks.onPeriod);
 
   return newPop
 }
@@ -0,0 +1,40 @@
+// src/params.test.ts
+import { describe, it, expect } from 'vitest'
+import { mergeParams } from './params'
+import { defaultParams } from './model'
+
+describe('Parameter Overrides - params.ts', () => {
+  describe('mergeParams', () => {
+    it('should return the defaults without overrides', () => {
+      expect(mergeParams({})).toEqual(defaultParams)
+    })
+
+    it('should override top-level values', () => {
+      const params = mergeParams({ populationSize: 100, seed: 7 })
+      expect(params.populationSize).toBe(100)
+      expect(params.seed).toBe(7)
+      expect(params.geneEnvWeight).toBe(defaultParams.geneEnvWeight)
+    })
+
+    it('should merge nested groups key by key', () => {
+      const params = mergeParams({ genetics: { nLoci: 10 }, homophily: { wealth: 0.5 } })
+      expect(params.genetics.nLoci).toBe(10)
+      expect(params.genetics.mutationRate).toBe(defaultParams.genetics.mutationRate)
+      expect(params.homophily).toEqual({ ...defaultParams.homophily, wealth: 0.5 })
+    })
+
+    it('should not modify the defaults', () => {
+      mergeParams({ genetics: { nLoci: 10 } })
+      expect(defaultParams.genetics.nLoci).toBe(1)
+    })
+
+    it('should reject unknown keys', () => {
+      expect(() => mergeParams({ populationSzie: 100 })).toThrow('populationSzie')
+      expect(() => mergeParams({ genetics: { nloci: 3 } })).toThrow('genetics.nloci')
+    })
+
+    it('should reject a non-object group', () => {
+      expect(() => mergeParams({ homophily: 0.5 })).toThrow('homophily')
+    })
+  })
+})
@@ -0,0 +1,54 @@
+// src/params.ts
+// Building a full Params object from a partial JSON override.
+import { Params, defaultParams, MATING_TRAITS } from './model'
+
+type Group = 'homophily' | 'fertility' | 'genetics' | 'kd'
+
+const GROUPS: Group[] = ['homophily', 'fertility', 'genetics', 'kd']
+
+/** Optional keys that defaultParams leaves out */
+const OPTIONAL_KEYS: Record<Group | 'top', string[]> = {
+  top:       ['seed', 'kd'],
+  homophily: MATING_TRAITS,
+  fertility: [],
+  genetics:  ['effectSizes'],
+  kd:        ['kNeighbors'],
+}
+
+const isObject = (v: unknown): v is Record<string, unknown> =>
+  typeof v === 'object' && v !== null && !Array.isArray(v)
+
+function checkKeys(overrides: Record<string, unknown>, base: object, optional: string[], path: string) {
+  Object.keys(overrides).forEach(key => {
+    if (!(key in base) && !optional.includes(key)) {
+      throw new Error(`Unknown parameter: ${path}${key}`)
+    }
+  })
+}
+
+/**
+ * `base` (the defaults) overridden by a partial params object, e.g. parsed from
+ * a JSON file. Nested groups (homophily, fertility, genetics, kd) merge key by
+ * key. Unknown keys throw, so a typo can't silently fall back to a default.
+ */
+export function mergeParams(overrides: Record<string, unknown>, base: Params = defaultParams): Params {
+  checkKeys(overrides, base, OPTIONAL_KEYS.top, '')
+  const merged: Record<string, unknown> = {
+    ...base,
+    homophily: { ...base.homophily },
+    fertility: { ...base.fertility },
+    genetics: { ...base.genetics },
+  }
+  Object.entries(overrides).forEach(([key, value]) => {
+    const group = GROUPS.find(g => g === key)
+    if (!group) {
+      merged[key] = value
+      return
+    }
+    if (!isObject(value)) throw new Error(`Parameter ${key} must be an object`)
+    const current = (base[group] ?? {}) as object
+    checkKeys(value, current, OPTIONAL_KEYS[group], `${key}.`)
+    merged[key] = { ...current, ...value }
+  })
+  return merged as unknown as Params
+}
@@ -0,0 +1,138 @@
+// src/rng.test.ts
+import { describe, it, expect } from 'vitest'
+import { createRng } from './rng'
+import {
+  defaultParams,
+  initializePopulation,
+  nextGeneration,
+  type Agent,
+  type Params
+} from './model'
+
+function runModel(params: Params, seed: number, generations: number): Agent[] {
+  const rng = createRng(seed)
+  let pop = initializePopulation(params, rng)
+  for (let g = 0; g < generations; g++) {
+    pop = nextGeneration(pop, params, rng)
+  }
+  return pop
+}
+
+/** Strip the parent snapshots so populations compare field by field */
+function flatten(pop: Agent[]) {
+  return pop.map(({ parents, ...rest }) => ({
+    ...rest,
+    parentIds: parents ? parents.map(p => p.id) : null
+  }))
+}
+
+describe('Random Number Generator', () => {
+  describe('createRng', () => {
+    it('should produce identical streams for identical seeds', () => {
+      const a = createRng(42)
+      const b = createRng(42)
+      for (let i = 0; i < 1000; i++) {
+        expect(a.random()).toBe(b.random())
+      }
+    })
+
+    it('should produce different streams for different seeds', () => {
+      const a = createRng(1)
+      const b = createRng(2)
+      const drawsA = Array.from({ length: 10 }, () => a.random())
+      const drawsB = Array.from({ length: 10 }, () => b.random())
+      expect(drawsA).not.toEqual(drawsB)
+    })
+
+    it('should keep uniform draws in [0, 1)', () => {
+      const rng = createRng(7)
+      for (let i = 0; i < 10000; i++) {
+        const u = rng.random()
+        expect(u).toBeGreaterThanOrEqual(0)
+        expect(u).toBeLessThan(1)
+      }
+    })
+
+    it('should keep integer draws in [0, n)', () => {
+      const rng = createRng(7)
+      const seen = new Set<number>()
+      for (let i = 0; i < 1000; i++) {
+        const k = rng.int(5)
+        expect(Number.isInteger(k)).toBe(true)
+        seen.add(k)
+      }
+      expect([...seen].sort()).toEqual([0, 1, 2, 3, 4])
+    })
+
+    it('should draw normals with the requested moments', () => {
+      const rng = createRng(11)
+      const n = 20000
+      const xs = Array.from({ length: n }, () => rng.normal(3, 2))
+      const mean = xs.reduce((s, x) => s + x, 0) / n
+      const sd = Math.sqrt(xs.reduce((s, x) => s + (x - mean) ** 2, 0) / (n - 1))
+      expect(mean).toBeCloseTo(3, 1)
+      expect(sd).toBeCloseTo(2, 1)
+    })
+
+    it('should draw betas with the requested mean', () => {
+      const rng = createRng(13)
+      const n = 20000
+      const xs = Array.from({ length: n }, () => rng.beta(2, 6))
+      const mean = xs.reduce((s, x) => s + x, 0) / n
+      xs.forEach(x => {
+        expect(x).toBeGreaterThanOrEqual(0)
+        expect(x).toBeLessThanOrEqual(1)
+      })
+      expect(mean).toBeCloseTo(0.25, 2)
+    })
+
+    it('should handle beta shapes below one', () => {
+      const rng = createRng(17)
+      const n = 20000
+      const xs = Array.from({ length: n }, () => rng.beta(0.01, 1))
+      const mean = xs.reduce((s, x) => s + x, 0) / n
+      expect(mean).toBeCloseTo(0.01 / 1.01, 2)
+    })
+
+    it('should draw Poisson counts with the requested mean', () => {
+      const rng = createRng(19)
+      const n = 20000
+      for (const lambda of [0.5, 2.2, 45]) {
+        const xs = Array.from({ length: n }, () => rng.poisson(lambda))
+        const mean = xs.reduce((s, x) => s + x, 0) / n
+        xs.forEach(x => {
+          expect(Number.isInteger(x)).toBe(true)
+          expect(x).toBeGreaterThanOrEqual(0)
+        })
+        expect(Math.abs(mean - lambda)).toBeLessThan(0.05 * lambda + 0.02)
+      }
+      expect(rng.poisson(0)).toBe(0)
+    })
+  })
+
+  describe('model reproducibility', () => {
+    const params: Params = {
+      ...defaultParams,
+      populationSize: 200,
+      homophily: { gene: 0.5, env: 0.5 }
+    }
+
+    it('should initialize bit-identical populations for identical seeds', () => {
+      const a = initializePopulation(params, createRng(123))
+      const b = initializePopulation(params, createRng(123))
+      expect(flatten(a)).toEqual(flatten(b))
+    })
+
+    it('should evolve bit-identical populations for identical seeds', () => {
+      const a = runModel(params, 2024, 5)
+      const b = runModel(params, 2024, 5)
+      expect(flatten(a)).toEqual(flatten(b))
+    })
+
+    it('should evolve different populations for different seeds', () => {
+      const a = runModel(params, 1, 3)
+      const b = runModel(params, 2, 3)
+      expect(flatten(a).map(x => x.wealth)).not.toEqual(flatten(b).map(x => x.wealth))
+    })
+  })
+})
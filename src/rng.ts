@@ -0,0 +1,120 @@
+// src/rng.ts
+// Seedable pseudo-random number generator shared by every stochastic step of the model.
+
+/**
+ * A deterministic source of randomness. Two generators created from the same
+ * seed produce identical streams, so runs can be reproduced bit for bit.
+ */
+export interface Rng {
+  /** Uniform draw on [0, 1) */
+  random(): number
+  /** Uniform integer on [0, n) */
+  int(n: number): number
+  /** Normal(mean, sd) draw */
+  normal(mean?: number, sd?: number): number
+  /** Gamma(shape, scale) draw */
+  gamma(shape: number, scale?: number): number
+  /** Beta(a, b) draw */
+  beta(a: number, b: number): number
+  /** Poisson(lambda) draw */
+  poisson(lambda: number): number
+}
+
+/** splitmix32: spreads an arbitrary integer seed over the generator state */
+function splitmix32(seed: number): () => number {
+  let s = seed >>> 0
+  return () => {
+    s = (s + 0x9e3779b9) >>> 0
+    let z = s
+    z = Math.imul(z ^ (z >>> 16), 0x85ebca6b)
+    z = Math.imul(z ^ (z >>> 13), 0xc2b2ae35)
+    return (z ^ (z >>> 16)) >>> 0
+  }
+}
+
+/**
+ * Create a generator (sfc32 core) from an integer seed.
+ * Without a seed, one is drawn from Math.random so unseeded runs still differ.
+ */
+export function createRng(seed?: number): Rng {
+  const init = splitmix32(seed ?? Math.floor(Math.random() * 2 ** 32))
+  let a = init(), b = init(), c = init(), d = init()
+
+  function random(): number {
+    const t = (((a + b) >>> 0) + d) >>> 0
+    d = (d + 1) >>> 0
+    a = b ^ (b >>> 9)
+    b = (c + (c << 3)) >>> 0
+    c = (c << 21) | (c >>> 11)
+    c = (c + t) >>> 0
+    return t / 4294967296
+  }
+
+  // Box–Muller produces normals in pairs; keep the spare for the next call
+  let spare: number | null = null
+  function standardNormal(): number {
+    if (spare !== null) {
+      const z = spare
+      spare = null
+      return z
+    }
+    let u = 0
+    while (u === 0) u = random()
+    const v = random()
+    const r = Math.sqrt(-2 * Math.log(u))
+    spare = r * Math.sin(2 * Math.PI * v)
+    return r * Math.cos(2 * Math.PI * v)
+  }
+
+  // Marsaglia–Tsang; shape < 1 is boosted via Gamma(shape + 1) · U^(1/shape)
+  function gamma(shape: number, scale = 1): number {
+    if (shape < 1) {
+      let u = 0
+      while (u === 0) u = random()
+      return gamma(shape + 1, scale) * Math.pow(u, 1 / shape)
+    }
+    const d3 = shape - 1 / 3
+    const c3 = 1 / Math.sqrt(9 * d3)
+    for (;;) {
+      let x: number, v: number
+      do {
+        x = standardNormal()
+        v = 1 + c3 * x
+      } while (v <= 0)
+      v = v * v * v
+      const u = random()
+      if (u < 1 - 0.0331 * x * x * x * x) return d3 * v * scale
+      if (Math.log(u) < 0.5 * x * x + d3 * (1 - v + Math.log(v))) return d3 * v * scale
+    }
+  }
+
+  // Knuth's product method; large means fall back to a rounded normal
+  function poisson(lambda: number): number {
+    if (lambda <= 0) return 0
+    if (lambda > 30) return Math.max(0, Math.round(lambda + Math.sqrt(lambda) * standardNormal()))
+    const L = Math.exp(-lambda)
+    let k = 0
+    let p = random()
+    while (p > L) {
+      k++
+      p *= random()
+    }
+    return k
+  }
+
+  return {
+    random,
+    int: n => Math.floor(random() * n),
+    normal: (mean = 0, sd = 1) => mean + sd * standardNormal(),
+    gamma,
+    beta: (alpha, beta) => {
+      const x = gamma(alpha)
+      const y = gamma(beta)
+      return x + y === 0 ? 0 : x / (x + y)
+    },
+    poisson,
+  }
+}
+
+/** Fallback generator for callers that do not pass their own */
+export const defaultRng: Rng = createRng()
@@ -0,0 +1,54 @@
+// src/run.test.ts
+import { describe, it, expect } from 'vitest'
+import { runSimulation } from './run'
+import { defaultParams, type Params } from './model'
+
+const params: Params = { ...defaultParams, populationSize: 200, seed: 42 }
+
+describe('Headless Runs - run.ts', () => {
+  describe('runSimulation', () => {
+    it('should record the founders and every generation', () => {
+      const history = runSimulation(params, { generations: 3 })
+      expect(history.gini).toHaveLength(4)
+      expect(history.meanEducation).toHaveLength(4)
+      // no parents among the founders
+      expect(history.ige[0]).toBeNaN()
+      expect(Number.isFinite(history.ige[3])).toBe(true)
+      expect(history.partnered[3]).toBeGreaterThan(0)
+    })
+
+    it('should be reproducible from the seed', () => {
+      const a = runSimulation(params, { generations: 3 })
+      const b = runSimulation(params, { generations: 3 })
+      expect(a).toEqual(b)
+    })
+
+    it('should call back once per generation', () => {
+      const seen: number[] = []
+      runSimulation(params, {
+        generations: 2,
+        onGeneration: (pop, generation) => {
+          seen.push(generation)
+          expect(pop.length).toBeGreaterThan(0)
+        },
+      })
+      expect(seen).toEqual([0, 1, 2])
+    })
+
+    it('should record NaN for the generations after extinction', () => {
+      const childless: Params = { ...params, fertility: { ...params.fertility, meanChildren: 0 } }
+      const history = runSimulation(childless, { generations: 4 })
+      expect(history.gini).toHaveLength(5)
+      expect(Number.isFinite(history.gini[0])).toBe(true)
+      history.gini.slice(1).forEach(g => expect(g).toBeNaN())
+    })
+
+    it('should stop early when stopWhen returns true', () => {
+      const history = runSimulation(params, {
+        generations: 10,
+        stopWhen: (h, generation) => generation === 4 && h.gini.length === 5,
+      })
+      expect(history.gini).toHaveLength(5)
+    })
+  })
+})
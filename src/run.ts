@@ -0,0 +1,50 @@
+// src/run.ts
+// Headless simulation loop: the browser's tick() without timers or drawing.
+import {
+  Agent,
+  Params,
+  PairingStats,
+  initializePopulation,
+  nextGeneration,
+  computePairingStats,
+} from './model'
+import { createRng } from './rng'
+import { History, appendRecord } from './metrics/history'
+import { generationRecord } from './metrics/record'
+
+export interface RunOptions {
+  generations: number
+  /** Called with the founders (generation 0) and after every generation */
+  onGeneration?: (pop: Agent[], generation: number) => void
+  /** Checked after every generation; returning true ends the run early */
+  stopWhen?: (history: History, generation: number) => boolean
+}
+
+/**
+ * Run up to `generations` generations from a fresh population seeded by
+ * params.seed and return the per-generation metrics (index 0 = founders).
+ * If the population dies out, the remaining generations are recorded as NaN
+ * so extinct runs keep the same length as the others.
+ */
+export function runSimulation(params: Params, { generations, onGeneration, stopWhen }: RunOptions): History {
+  const rng = createRng(params.seed)
+  let pop = initializePopulation(params, rng)
+  const history: History = {}
+  appendRecord(history, generationRecord(pop))
+  onGeneration?.(pop, 0)
+
+  for (let generation = 1; generation <= generations; generation++) {
+    let pairing: PairingStats | null = null
+    pop = nextGeneration(pop, params, rng, {
+      onPairing: (pairs, parents) => { pairing = computePairingStats(parents, pairs) },
+    })
+    if (pop.length === 0) {
+      for (let g = generation; g <= generations; g++) appendRecord(history, {})
+      break
+    }
+    appendRecord(history, generationRecord(pop, pairing))
+    onGeneration?.(pop, generation)
+    if (stopWhen?.(history, generation)) break
+  }
+  return history
+}
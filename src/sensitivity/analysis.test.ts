@@ -0,0 +1,82 @@
+// src/sensitivity/analysis.test.ts
+import { describe, it, expect } from 'vitest'
+import { DEFAULT_FACTORS, steadyState, scalePoint, runSensitivity, indicesToCSV } from './analysis'
+
+const small = {
+  base: { populationSize: 50 },
+  parameters: { geneEnvWeight: { min: 0, max: 1 }, 'homophily.wealth': { min: -1, max: 1 } },
+  generations: 2,
+  window: 2,
+  bootstrap: 10,
+}
+
+describe('Sensitivity Analysis - analysis.ts', () => {
+  describe('steadyState', () => {
+    it('should average the finite tail', () => {
+      expect(steadyState({ gini: [0.1, 0.2, 0.4, NaN, 0.6] }, 'gini', 3)).toBeCloseTo(0.5)
+      expect(steadyState({ gini: [NaN] }, 'gini', 3)).toBeNaN()
+      expect(steadyState({}, 'gini', 3)).toBeNaN()
+    })
+  })
+
+  describe('scalePoint', () => {
+    it('should map the unit cube onto the ranges', () => {
+      expect(scalePoint([0.5, 0.25], small.parameters)).toEqual({ geneEnvWeight: 0.5, 'homophily.wealth': -0.5 })
+    })
+
+    it('should cover the requested levers by default', () => {
+      expect(Object.keys(DEFAULT_FACTORS)).toEqual(expect.arrayContaining([
+        'geneEnvWeight', 'envNoiseStd', 'financeWeight', 'financeNoise', 'homophily.gene', 'homophily.env',
+      ]))
+    })
+
+    it('should keep the default homophily ranges assortative', () => {
+      Object.entries(DEFAULT_FACTORS)
+        .filter(([name]) => name.startsWith('homophily.'))
+        .forEach(([, range]) => expect(range).toEqual({ min: 0, max: 1 }))
+    })
+  })
+
+  describe('runSensitivity', () => {
+    it('should produce S1 and ST per output and factor', async () => {
+      const rows = await runSensitivity({ ...small, samples: 4 }, { workers: 1 })
+      expect(rows).toHaveLength(2 * 2 * 2)
+      expect(rows.filter(r => r.output === 'gini' && r.index === 'ST')).toHaveLength(2)
+    })
+
+    it('should produce Morris statistics', async () => {
+      const seen: number[] = []
+      const rows = await runSensitivity(
+        { ...small, method: 'morris', trajectories: 3, outputs: ['gini'] },
+        { workers: 1, onProgress: done => seen.push(done) }
+      )
+      expect(rows.map(r => r.index)).toEqual(['mu', 'muStar', 'sigma', 'mu', 'muStar', 'sigma'])
+      rows.forEach(r => {
+        expect(r.lo).not.toBeNaN()
+        expect(r.hi).not.toBeNaN()
+      })
+      // 3 trajectories × (2 factors + 1) runs
+      expect(seen).toHaveLength(9)
+    })
+
+    it('should reject fewer than two Morris levels before running', async () => {
+      const seen: number[] = []
+      await expect(runSensitivity(
+        { ...small, method: 'morris', levels: 1 },
+        { workers: 1, onProgress: done => seen.push(done) }
+      )).rejects.toThrow('levels')
+      expect(seen).toHaveLength(0)
+    })
+
+    it('should reject unknown methods', async () => {
+      await expect(runSensitivity({ ...small, method: 'fast' as any }, { workers: 1 })).rejects.toThrow('fast')
+    })
+  })
+
+  describe('indicesToCSV', () => {
+    it('should write one line per index', () => {
+      const csv = indicesToCSV([{ output: 'gini', parameter: 'geneEnvWeight', index: 'S1', estimate: 0.5, lo: 0.4, hi: 0.6 }])
+      expect(csv).toBe('output,parameter,index,estimate,lo,hi\ngini,geneEnvWeight,S1,0.5,0.4,0.6\n')
+    })
+  })
+})
@@ -0,0 +1,162 @@
+// src/sensitivity/analysis.ts
+// Global sensitivity of steady-state outcomes to model parameters.
+import { Params } from '../model'
+import { createRng } from '../rng'
+import { History } from '../metrics/history'
+import { mean } from '../metrics/stats'
+import { mergeParams } from '../params'
+import { csvRow } from '../csv'
+import { pointOverrides, SweepPoint } from '../sweep/design'
+import { SweepJob, SweepResult } from '../sweep/jobs'
+import { PoolOptions, runJobs } from '../sweep/pool'
+import { saltelliDesign, sobolWithBootstrap } from './sobol'
+import { morrisDesign, morrisWithBootstrap } from './morris'
+
+export type FactorRanges = Record<string, { min: number, max: number }>
+
+/**
+ * The education, finance and mating levers, over their slider ranges. Homophily
+ * is assortative only: a negative weight makes the marriage market fall back to
+ * an O(N²) linear scan, so disassortative ranges must be asked for explicitly.
+ */
+export const DEFAULT_FACTORS: FactorRanges = {
+  geneEnvWeight:            { min: 0, max: 1 },
+  envNoiseStd:              { min: 0, max: 2 },
+  financeWeight:            { min: 0, max: 1 },
+  financeNoise:             { min: 0, max: 2 },
+  'homophily.gene':         { min: 0, max: 1 },
+  'homophily.env':          { min: 0, max: 1 },
+  'homophily.education':    { min: 0, max: 1 },
+  'homophily.wealth':       { min: 0, max: 1 },
+  'homophily.parentWealth': { min: 0, max: 1 },
+}
+
+export interface SensitivitySpec {
+  /** Overrides applied to the defaults at every point */
+  base?: Record<string, unknown>
+  /** Factors and their ranges (dotted paths into nested groups); DEFAULT_FACTORS if absent */
+  parameters?: FactorRanges
+  method?: 'sobol' | 'morris'
+  /** Sobol base sample size n; the model runs n (k + 2) times */
+  samples?: number
+  /** Morris trajectories r and grid levels p; the model runs r (k + 1) times */
+  trajectories?: number
+  levels?: number
+  generations?: number
+  /** Steady state = mean over the last `window` generations */
+  window?: number
+  /** History metrics to analyze */
+  outputs?: string[]
+  bootstrap?: number
+  seed?: number
+}
+
+/** One line of the index table */
+export interface IndexRow {
+  output: string
+  parameter: string
+  /** S1 / ST (Sobol) or mu / muStar / sigma (Morris) */
+  index: string
+  estimate: number
+  lo: number
+  hi: number
+}
+
+/** Mean of the finite values of `metric` over the last `window` generations */
+export function steadyState(history: History, metric: string, window: number): number {
+  const tail = (history[metric] ?? []).slice(-window).filter(Number.isFinite)
+  return tail.length > 0 ? mean(tail) : NaN
+}
+
+/** A unit-cube point mapped onto the factor ranges */
+export function scalePoint(unit: number[], factors: FactorRanges): SweepPoint {
+  const point: SweepPoint = {}
+  Object.entries(factors).forEach(([name, { min, max }], i) => {
+    point[name] = min + unit[i] * (max - min)
+  })
+  return point
+}
+
+/**
+ * Run the design and estimate the indices. Runs of the same Sobol row or Morris
+ * trajectory share a seed, so differences between them come from the factors.
+ */
+export async function runSensitivity(
+  spec: SensitivitySpec,
+  pool: Omit<PoolOptions, 'onResult'> & { onProgress?: (done: number, total: number) => void }
+): Promise<IndexRow[]> {
+  if (spec.method !== undefined && spec.method !== 'sobol' && spec.method !== 'morris') {
+    throw new Error(`Unknown sensitivity method '${spec.method}' (use "sobol" or "morris")`)
+  }
+  const factors = spec.parameters ?? DEFAULT_FACTORS
+  const names = Object.keys(factors)
+  const k = names.length
+  const outputs = spec.outputs ?? ['gini', 'rankRankSlope']
+  const generations = spec.generations ?? 50
+  const window = spec.window ?? 10
+  const nBoot = spec.bootstrap ?? 200
+  const firstSeed = spec.seed ?? 1
+  const rng = createRng(firstSeed)
+  const base: Params = mergeParams(spec.base ?? {})
+
+  // unit points and the seed of each
+  const units: number[][] = []
+  const seeds: number[] = []
+  const sobol = spec.method !== 'morris'
+  const n = spec.samples ?? 64
+  const saltelli = sobol ? saltelliDesign(k, n, rng) : null
+  const morris = sobol ? null : morrisDesign(k, spec.trajectories ?? 20, spec.levels ?? 4, rng)
+  if (saltelli) {
+    ;[saltelli.A, saltelli.B, ...saltelli.AB].forEach(matrix => matrix.forEach((row, j) => {
+      units.push(row)
+      seeds.push(firstSeed + j)
+    }))
+  } else {
+    morris!.trajectories.forEach((points, t) => points.forEach(row => {
+      units.push(row)
+      seeds.push(firstSeed + t)
+    }))
+  }
+
+  const jobs: SweepJob[] = units.map((unit, i) => ({
+    point: i,
+    replicate: 0,
+    seed: seeds[i],
+    generations,
+    params: { ...mergeParams(pointOverrides(scalePoint(unit, factors)), base), seed: seeds[i] },
+  }))
+  let done = 0
+  const results: SweepResult[] = await runJobs(jobs, {
+    ...pool,
+    onResult: () => { done += 1; pool.onProgress?.(done, jobs.length) },
+  })
+
+  const rows: IndexRow[] = []
+  outputs.forEach(output => {
+    const y = results.map(r => steadyState(r.history, output, window))
+    if (saltelli) {
+      // y is laid out as A, B, AB₀, …, AB_{k−1}, n rows each
+      const block = (b: number) => y.slice(b * n, (b + 1) * n)
+      const result = sobolWithBootstrap(block(0), block(1), names.map((_, i) => block(i + 2)), nBoot, rng)
+      names.forEach((parameter, i) => {
+        rows.push({ output, parameter, index: 'S1', ...result.first[i] })
+        rows.push({ output, parameter, index: 'ST', ...result.total[i] })
+      })
+    } else {
+      const perTrajectory = morris!.trajectories.map((_, t) => y.slice(t * (k + 1), (t + 1) * (k + 1)))
+      const result = morrisWithBootstrap(morris!, perTrajectory, nBoot, rng)
+      names.forEach((parameter, i) => {
+        rows.push({ output, parameter, index: 'mu', ...result.mu[i] })
+        rows.push({ output, parameter, index: 'muStar', ...result.muStar[i] })
+        rows.push({ output, parameter, index: 'sigma', ...result.sigma[i] })
+      })
+    }
+  })
+  return rows
+}
+
+/** The index table as CSV */
+export function indicesToCSV(rows: IndexRow[]): string {
+  return csvRow(['output', 'parameter', 'index', 'estimate', 'lo', 'hi'])
+    + rows.map(r => csvRow([r.output, r.parameter, r.index, r.estimate, r.lo, r.hi])).join('')
+}
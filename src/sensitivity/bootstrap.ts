@@ -0,0 +1,36 @@
+// src/sensitivity/bootstrap.ts
+// Percentile bootstrap shared by the Sobol and Morris estimators.
+import { Rng } from '../rng'
+import { quantile } from '../metrics/inequality'
+
+/** An estimate with a percentile bootstrap confidence interval */
+export interface Estimate {
+  estimate: number
+  lo: number
+  hi: number
+}
+
+/**
+ * Resample `n` units with replacement `nBoot` times and return the
+ * (1 − level)/2 and (1 + level)/2 percentiles of each statistic. `statistics`
+ * maps a list of unit indices to a vector of estimates (e.g. one per factor).
+ */
+export function bootstrapIntervals(
+  n: number,
+  statistics: (indices: number[]) => number[],
+  nBoot: number,
+  rng: Rng,
+  level = 0.95
+): { lo: number[], hi: number[] } {
+  const draws: number[][] = []
+  for (let b = 0; b < nBoot; b++) {
+    const indices = Array.from({ length: n }, () => rng.int(n))
+    draws.push(statistics(indices))
+  }
+  const width = draws[0]?.length ?? 0
+  const column = (i: number) => draws.map(d => d[i]).filter(Number.isFinite)
+  return {
+    lo: Array.from({ length: width }, (_, i) => quantile(column(i), (1 - level) / 2)),
+    hi: Array.from({ length: width }, (_, i) => quantile(column(i), (1 + level) / 2)),
+  }
+}
@@ -0,0 +1,81 @@
+// src/sensitivity/morris.test.ts
+import { describe, it, expect } from 'vitest'
+import { morrisDesign, elementaryEffects, morrisWithBootstrap } from './morris'
+import { createRng } from '../rng'
+
+describe('Morris Screening - morris.ts', () => {
+  describe('morrisDesign', () => {
+    it('should move one factor by ±Δ per step, staying in the unit cube', () => {
+      const design = morrisDesign(4, 10, 4, createRng(1))
+      expect(design.delta).toBeCloseTo(2 / 3)
+      expect(design.trajectories).toHaveLength(10)
+
+      design.trajectories.forEach((points, t) => {
+        expect(points).toHaveLength(5)
+        expect([...design.order[t]].sort()).toEqual([0, 1, 2, 3])
+        points.forEach(p => p.forEach(x => {
+          expect(x).toBeGreaterThanOrEqual(-1e-12)
+          expect(x).toBeLessThanOrEqual(1 + 1e-12)
+        }))
+        design.order[t].forEach((i, s) => {
+          points[s].forEach((x, c) => {
+            const step = points[s + 1][c] - x
+            if (c === i) expect(Math.abs(step)).toBeCloseTo(design.delta)
+            else expect(step).toBe(0)
+          })
+        })
+      })
+    })
+
+    it('should reject fewer than two levels', () => {
+      expect(() => morrisDesign(2, 5, 1, createRng(1))).toThrow('levels')
+      expect(() => morrisDesign(2, 5, 2.5, createRng(1))).toThrow('levels')
+    })
+  })
+
+  const linear = (x: number[]) => 3 * x[0] - 2 * x[1]
+
+  describe('elementaryEffects', () => {
+    it('should recover the slopes of a linear model', () => {
+      const design = morrisDesign(3, 8, 4, createRng(2))
+      const outputs = design.trajectories.map(points => points.map(linear))
+      const effects = elementaryEffects(design, outputs)
+
+      effects[0].forEach(e => expect(e).toBeCloseTo(3))
+      effects[1].forEach(e => expect(e).toBeCloseTo(-2))
+      effects[2].forEach(e => expect(e).toBeCloseTo(0))
+    })
+  })
+
+  describe('morrisWithBootstrap', () => {
+    it('should summarize a linear model without spread', () => {
+      const design = morrisDesign(3, 8, 4, createRng(3))
+      const outputs = design.trajectories.map(points => points.map(linear))
+      const result = morrisWithBootstrap(design, outputs, 50, createRng(4))
+
+      expect(result.mu[1].estimate).toBeCloseTo(-2)
+      expect(result.mu[1].lo).toBeCloseTo(-2)
+      expect(result.mu[1].hi).toBeCloseTo(-2)
+      expect(result.muStar[1].estimate).toBeCloseTo(2)
+      expect(result.muStar[1].lo).toBeCloseTo(2)
+      expect(result.muStar[1].hi).toBeCloseTo(2)
+      result.sigma.forEach(s => {
+        expect(s.estimate).toBeCloseTo(0)
+        expect(s.hi).toBeCloseTo(0)
+      })
+    })
+
+    it('should show interactions as spread', () => {
+      const design = morrisDesign(2, 20, 4, createRng(5))
+      const outputs = design.trajectories.map(points => points.map(x => x[0] * x[1]))
+      const result = morrisWithBootstrap(design, outputs, 50, createRng(6))
+
+      expect(result.sigma[0].estimate).toBeGreaterThan(0.1)
+      ;[result.mu[0], result.muStar[0], result.sigma[0]].forEach(({ estimate, lo, hi }) => {
+        expect(lo).toBeLessThan(hi)
+        expect(lo).toBeLessThanOrEqual(estimate)
+        expect(hi).toBeGreaterThanOrEqual(estimate)
+      })
+    })
+  })
+})
@@ -0,0 +1,107 @@
+// src/sensitivity/morris.ts
+// Morris elementary-effects screening.
+import { Rng } from '../rng'
+import { mean, variance } from '../metrics/stats'
+import { Estimate, bootstrapIntervals } from './bootstrap'
+
+/**
+ * r random one-at-a-time trajectories on a p-level grid of the unit hypercube.
+ * Each trajectory has k + 1 points and moves every factor once, in random
+ * order and direction, by Δ = p / (2 (p − 1)).
+ */
+export interface MorrisDesign {
+  trajectories: number[][][]
+  /** Factor moved between point s and s + 1 of each trajectory */
+  order: number[][]
+  delta: number
+}
+
+export function morrisDesign(k: number, r: number, levels: number, rng: Rng): MorrisDesign {
+  if (!Number.isInteger(levels) || levels < 2) {
+    throw new Error(`Morris designs need an integer number of levels ≥ 2, got ${levels}`)
+  }
+  const delta = levels / (2 * (levels - 1))
+  const grid = Array.from({ length: levels }, (_, l) => l / (levels - 1))
+  const trajectories: number[][][] = []
+  const order: number[][] = []
+
+  for (let t = 0; t < r; t++) {
+    // moving up needs x ≤ 1 − Δ, moving down x ≥ Δ
+    const up = Array.from({ length: k }, () => rng.random() < 0.5)
+    let x = up.map(u => {
+      const allowed = grid.filter(g => u ? g <= 1 - delta + 1e-12 : g >= delta - 1e-12)
+      return allowed[rng.int(allowed.length)]
+    })
+    const perm = Array.from({ length: k }, (_, i) => i)
+    for (let i = k - 1; i > 0; i--) {
+      const j = rng.int(i + 1)
+      ;[perm[i], perm[j]] = [perm[j], perm[i]]
+    }
+    const points = [x]
+    perm.forEach(i => {
+      x = x.map((v, c) => c === i ? v + (up[i] ? delta : -delta) : v)
+      points.push(x)
+    })
+    trajectories.push(points)
+    order.push(perm)
+  }
+  return { trajectories, order, delta }
+}
+
+/** Elementary effects per factor (one per trajectory), in unit-cube units */
+export function elementaryEffects(design: MorrisDesign, outputs: number[][]): number[][] {
+  const k = design.order[0]?.length ?? 0
+  const effects: number[][] = Array.from({ length: k }, () => [])
+  design.trajectories.forEach((points, t) => {
+    design.order[t].forEach((i, s) => {
+      const step = points[s + 1][i] - points[s][i]
+      effects[i].push((outputs[t][s + 1] - outputs[t][s]) / step)
+    })
+  })
+  return effects
+}
+
+/** Morris statistics per factor, each with a bootstrap interval */
+export interface MorrisResult {
+  /** Mean effect (signed; effects of opposite sign cancel) */
+  mu: Estimate[]
+  /** Mean absolute effect, the usual importance ranking */
+  muStar: Estimate[]
+  /** Spread of the effects: nonlinearity or interactions */
+  sigma: Estimate[]
+}
+
+/**
+ * Morris statistics from model outputs on a design (outputs[t][s] for point s
+ * of trajectory t), with bootstrap intervals from resampling the trajectories.
+ * Effects that aren't finite are dropped.
+ */
+export function morrisWithBootstrap(
+  design: MorrisDesign,
+  outputs: number[][],
+  nBoot: number,
+  rng: Rng,
+  level = 0.95
+): MorrisResult {
+  const effects = elementaryEffects(design, outputs)
+  const k = effects.length
+  const finite = (xs: number[]) => xs.filter(Number.isFinite)
+  // μ, μ* and σ of every factor, back to back
+  const statistics = (trajectories: number[]) => {
+    const sampled = effects.map(ee => finite(trajectories.map(t => ee[t])))
+    return [
+      ...sampled.map(ee => mean(ee)),
+      ...sampled.map(ee => mean(ee.map(Math.abs))),
+      ...sampled.map(ee => Math.sqrt(variance(ee))),
+    ]
+  }
+
+  const all = design.trajectories.map((_, t) => t)
+  const point = statistics(all)
+  const ci = bootstrapIntervals(all.length, statistics, nBoot, rng, level)
+  const estimates = (block: number) => Array.from({ length: k }, (_, i) => {
+    const j = block * k + i
+    return { estimate: point[j], lo: ci.lo[j], hi: ci.hi[j] }
+  })
+  return { mu: estimates(0), muStar: estimates(1), sigma: estimates(2) }
+}
@@ -0,0 +1,87 @@
+// src/sensitivity/sobol.test.ts
+import { describe, it, expect } from 'vitest'
+import { saltelliDesign, sobolIndices, sobolWithBootstrap } from './sobol'
+import { createRng } from '../rng'
+
+/** Ishigami function on the unit cube (inputs rescaled to [−π, π]) */
+const ishigami = (u: number[]) => {
+  const x = u.map(v => -Math.PI + 2 * Math.PI * v)
+  return Math.sin(x[0]) + 7 * Math.sin(x[1]) ** 2 + 0.1 * x[2] ** 4 * Math.sin(x[0])
+}
+
+const evaluate = (f: (u: number[]) => number, k: number, n: number, seed: number) => {
+  const design = saltelliDesign(k, n, createRng(seed))
+  return {
+    fA: design.A.map(f),
+    fB: design.B.map(f),
+    fAB: design.AB.map(m => m.map(f)),
+  }
+}
+
+describe('Sobol Indices - sobol.ts', () => {
+  describe('saltelliDesign', () => {
+    it('should take column i of ABᵢ from B and the rest from A', () => {
+      const { A, B, AB } = saltelliDesign(3, 5, createRng(1))
+      expect(A).toHaveLength(5)
+      expect(AB).toHaveLength(3)
+      AB.forEach((m, i) => m.forEach((row, j) => row.forEach((x, c) => {
+        expect(x).toBe(c === i ? B[j][c] : A[j][c])
+      })))
+    })
+  })
+
+  describe('sobolIndices', () => {
+    it('should recover the analytical Ishigami indices', () => {
+      const { fA, fB, fAB } = evaluate(ishigami, 3, 8192, 2)
+      const { first, total } = sobolIndices(fA, fB, fAB)
+
+      // analytical: S = (0.314, 0.442, 0), ST = (0.558, 0.442, 0.244)
+      expect(Math.abs(first[0] - 0.314)).toBeLessThan(0.08)
+      expect(Math.abs(first[1] - 0.442)).toBeLessThan(0.08)
+      expect(Math.abs(first[2])).toBeLessThan(0.08)
+      expect(Math.abs(total[0] - 0.558)).toBeLessThan(0.04)
+      expect(Math.abs(total[1] - 0.442)).toBeLessThan(0.04)
+      expect(Math.abs(total[2] - 0.244)).toBeLessThan(0.04)
+    })
+
+    it('should give equal first-order and total indices for an additive model', () => {
+      // variances 4 : 1 : 0 on the unit cube
+      const { fA, fB, fAB } = evaluate(u => 2 * u[0] + u[1], 3, 8192, 3)
+      const { first, total } = sobolIndices(fA, fB, fAB)
+
+      expect(total[0]).toBeCloseTo(0.8, 1)
+      expect(total[1]).toBeCloseTo(0.2, 1)
+      expect(total[2]).toBe(0)
+      first.forEach((s, i) => expect(Math.abs(s - total[i])).toBeLessThan(0.06))
+    })
+
+    it('should return NaN without output variance', () => {
+      const { first, total } = sobolIndices([1, 1], [1, 1], [[1, 1]])
+      expect(first[0]).toBeNaN()
+      expect(total[0]).toBeNaN()
+    })
+  })
+
+  describe('sobolWithBootstrap', () => {
+    it('should bracket the estimates', () => {
+      const { fA, fB, fAB } = evaluate(u => 2 * u[0] + u[1], 2, 256, 4)
+      const result = sobolWithBootstrap(fA, fB, fAB, 100, createRng(5))
+
+      ;[...result.first, ...result.total].forEach(({ estimate, lo, hi }) => {
+        expect(lo).toBeLessThanOrEqual(estimate + 0.05)
+        expect(hi).toBeGreaterThanOrEqual(estimate - 0.05)
+        expect(hi - lo).toBeLessThan(0.5)
+      })
+    })
+
+    it('should drop rows with non-finite outputs', () => {
+      const { fA, fB, fAB } = evaluate(u => 2 * u[0] + u[1], 2, 256, 4)
+      const clean = sobolWithBootstrap(fA, fB, fAB, 10, createRng(6))
+      fAB[1][0] = NaN
+      const withNaN = sobolWithBootstrap(fA, fB, fAB, 10, createRng(6))
+
+      expect(Number.isFinite(withNaN.total[0].estimate)).toBe(true)
+      expect(withNaN.total[0].estimate).toBeCloseTo(clean.total[0].estimate, 1)
+    })
+  })
+})
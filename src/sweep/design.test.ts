@@ -0,0 +1,91 @@
+// src/sweep/design.test.ts
+import { describe, it, expect } from 'vitest'
+import {
+  gridValues,
+  expandGrid,
+  latinHypercube,
+  designPoints,
+  pointOverrides,
+  validateSpec
+} from './design'
+import { createRng } from '../rng'
+
+describe('Sweep Designs - design.ts', () => {
+  describe('gridValues', () => {
+    it('should keep explicit lists', () => {
+      expect(gridValues(['equal', 'primogeniture'])).toEqual(['equal', 'primogeniture'])
+    })
+
+    it('should space a range evenly', () => {
+      expect(gridValues({ min: 0, max: 1, steps: 3 })).toEqual([0, 0.5, 1])
+      expect(gridValues({ min: -1, max: 1 })).toHaveLength(5)
+      expect(gridValues({ min: 2, max: 3, steps: 1 })).toEqual([2])
+    })
+  })
+
+  describe('expandGrid', () => {
+    it('should produce every combination', () => {
+      const points = expandGrid({ geneEnvWeight: [0, 1], 'homophily.wealth': [-1, 0, 1] })
+      expect(points).toHaveLength(6)
+      expect(points[0]).toEqual({ geneEnvWeight: 0, 'homophily.wealth': -1 })
+      expect(points[5]).toEqual({ geneEnvWeight: 1, 'homophily.wealth': 1 })
+    })
+  })
+
+  describe('latinHypercube', () => {
+    it('should use every stratum of every range exactly once', () => {
+      const n = 20
+      const points = latinHypercube({ a: { min: 0, max: 1 }, b: { min: 10, max: 20 } }, n, createRng(1))
+      expect(points).toHaveLength(n)
+
+      const strataA = points.map(p => Math.floor((p.a as number) * n)).sort((x, y) => x - y)
+      const strataB = points.map(p => Math.floor(((p.b as number) - 10) / 10 * n)).sort((x, y) => x - y)
+      const all = Array.from({ length: n }, (_, i) => i)
+      expect(strataA).toEqual(all)
+      expect(strataB).toEqual(all)
+    })
+
+    it('should sample lists by stratum', () => {
+      const points = latinHypercube({ mode: ['x', 'y'] }, 10, createRng(2))
+      expect(points.filter(p => p.mode === 'x')).toHaveLength(5)
+    })
+
+    it('should be reproducible from the seed', () => {
+      const spec = { a: { min: 0, max: 1 } }
+      expect(latinHypercube(spec, 5, createRng(3))).toEqual(latinHypercube(spec, 5, createRng(3)))
+    })
+  })
+
+  describe('designPoints', () => {
+    it('should default to a grid', () => {
+      expect(designPoints({ parameters: { a: [1, 2, 3] } }, createRng(1))).toHaveLength(3)
+    })
+
+    it('should draw `samples` hypercube points', () => {
+      const spec = { parameters: { a: { min: 0, max: 1 } }, design: 'lhs' as const, samples: 7 }
+      expect(designPoints(spec, createRng(1))).toHaveLength(7)
+    })
+  })
+
+  describe('pointOverrides', () => {
+    it('should nest dotted paths', () => {
+      expect(pointOverrides({ geneEnvWeight: 0.2, 'homophily.wealth': 0.5, 'homophily.gene': 1 }))
+        .toEqual({ geneEnvWeight: 0.2, homophily: { wealth: 0.5, gene: 1 } })
+    })
+  })
+
+  describe('validateSpec', () => {
+    it('should accept lists and ranges', () => {
+      const spec = { parameters: { a: [1, 2], b: { min: 0, max: 1 } } }
+      expect(validateSpec(spec)).toBe(spec)
+    })
+
+    it('should reject malformed specs', () => {
+      expect(() => validateSpec(null)).toThrow()
+      expect(() => validateSpec({ parameters: {} })).toThrow('parameters')
+      expect(() => validateSpec({ parameters: { a: [] } })).toThrow('a')
+      expect(() => validateSpec({ parameters: { a: { min: 0 } } })).toThrow('a')
+      expect(() => validateSpec({ parameters: { a: [1] }, design: 'sobol' })).toThrow('sobol')
+    })
+  })
+})
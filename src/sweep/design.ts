@@ -0,0 +1,108 @@
+// src/sweep/design.ts
+// Sweep specifications and the parameter points they expand to.
+import { Rng } from '../rng'
+
+export type ParamValue = number | string | boolean
+
+/** Values of one swept parameter: an explicit list, or a numeric range */
+export type Dimension = ParamValue[] | { min: number, max: number, steps?: number }
+
+/**
+ * A sweep, usually read from JSON. Parameter names are Params fields, with
+ * dotted paths into the nested groups (e.g. "homophily.wealth").
+ */
+export interface SweepSpec {
+  /** Overrides applied to the defaults at every point */
+  base?: Record<string, unknown>
+  parameters: Record<string, Dimension>
+  /** Full factorial grid (default) or a Latin hypercube of `samples` points */
+  design?: 'grid' | 'lhs'
+  samples?: number
+  replicates?: number
+  generations?: number
+  /** Replicate r runs with seed + r at every point (common random numbers) */
+  seed?: number
+}
+
+/** One parameter combination, keyed by parameter path */
+export type SweepPoint = Record<string, ParamValue>
+
+const isRange = (d: Dimension): d is { min: number, max: number, steps?: number } => !Array.isArray(d)
+
+/** Grid values of a dimension: the list itself, or `steps` evenly spaced values (default 5) */
+export function gridValues(dim: Dimension): ParamValue[] {
+  if (!isRange(dim)) return dim
+  const steps = dim.steps ?? 5
+  if (steps === 1) return [dim.min]
+  return Array.from({ length: steps }, (_, i) => dim.min + (i * (dim.max - dim.min)) / (steps - 1))
+}
+
+/** Every combination of the parameters' grid values, first parameter varying slowest */
+export function expandGrid(parameters: Record<string, Dimension>): SweepPoint[] {
+  return Object.entries(parameters).reduce<SweepPoint[]>(
+    (points, [name, dim]) => points.flatMap(p => gridValues(dim).map(v => ({ ...p, [name]: v }))),
+    [{}]
+  )
+}
+
+/**
+ * Latin hypercube of `samples` points: each parameter's range is cut into
+ * `samples` equal strata, each stratum is used exactly once, and strata are
+ * paired across parameters at random. Lists are sampled by stratum index.
+ */
+export function latinHypercube(parameters: Record<string, Dimension>, samples: number, rng: Rng): SweepPoint[] {
+  const points: SweepPoint[] = Array.from({ length: samples }, () => ({}))
+  Object.entries(parameters).forEach(([name, dim]) => {
+    // Fisher–Yates shuffle of the strata
+    const strata = Array.from({ length: samples }, (_, i) => i)
+    for (let i = samples - 1; i > 0; i--) {
+      const j = rng.int(i + 1)
+      ;[strata[i], strata[j]] = [strata[j], strata[i]]
+    }
+    strata.forEach((stratum, i) => {
+      const u = (stratum + rng.random()) / samples
+      points[i][name] = isRange(dim)
+        ? dim.min + u * (dim.max - dim.min)
+        : dim[Math.min(dim.length - 1, Math.floor(u * dim.length))]
+    })
+  })
+  return points
+}
+
+/** The points of a sweep design */
+export function designPoints(spec: SweepSpec, rng: Rng): SweepPoint[] {
+  return spec.design === 'lhs'
+    ? latinHypercube(spec.parameters, spec.samples ?? 10, rng)
+    : expandGrid(spec.parameters)
+}
+
+/** A point as a nested params override: { "homophily.wealth": 1 } → { homophily: { wealth: 1 } } */
+export function pointOverrides(point: SweepPoint): Record<string, unknown> {
+  const overrides: Record<string, any> = {}
+  Object.entries(point).forEach(([path, value]) => {
+    const keys = path.split('.')
+    let target = overrides
+    keys.slice(0, -1).forEach(k => { target = target[k] ??= {} })
+    target[keys[keys.length - 1]] = value
+  })
+  return overrides
+}
+
+/** Check the shape of a parsed sweep spec */
+export function validateSpec(raw: unknown): SweepSpec {
+  const spec = raw as SweepSpec
+  if (typeof spec !== 'object' || spec === null) throw new Error('Sweep spec must be an object')
+  if (typeof spec.parameters !== 'object' || spec.parameters === null || Object.keys(spec.parameters).length === 0) {
+    throw new Error('Sweep spec needs at least one entry in "parameters"')
+  }
+  if (spec.design !== undefined && spec.design !== 'grid' && spec.design !== 'lhs') {
+    throw new Error(`Unknown sweep design '${spec.design}' (use "grid" or "lhs")`)
+  }
+  Object.entries(spec.parameters).forEach(([name, dim]) => {
+    const ok = Array.isArray(dim)
+      ? dim.length > 0
+      : typeof dim === 'object' && dim !== null && typeof dim.min === 'number' && typeof dim.max === 'number'
+    if (!ok) throw new Error(`Parameter ${name} needs a non-empty list of values or a { "min", "max" } range`)
+  })
+  return spec
+}
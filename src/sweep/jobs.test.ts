@@ -0,0 +1,65 @@
+// src/sweep/jobs.test.ts
+import { describe, it, expect } from 'vitest'
+import { makeJobs, runJob } from './jobs'
+import { runJobs } from './pool'
+import { SweepSpec, expandGrid } from './design'
+
+const spec: SweepSpec = {
+  base: { populationSize: 100 },
+  parameters: { 'homophily.wealth': [0, 1], geneEnvWeight: [0.2] },
+  replicates: 3,
+  generations: 2,
+  seed: 10,
+}
+const points = expandGrid(spec.parameters)
+
+describe('Sweep Jobs - jobs.ts', () => {
+  describe('makeJobs', () => {
+    it('should make one job per point and replicate', () => {
+      const jobs = makeJobs(spec, points)
+      expect(jobs).toHaveLength(6)
+      expect(jobs.map(j => [j.point, j.replicate])).toEqual([[0, 0], [0, 1], [0, 2], [1, 0], [1, 1], [1, 2]])
+    })
+
+    it('should apply the base and the point to the defaults', () => {
+      const job = makeJobs(spec, points)[3]
+      expect(job.params.populationSize).toBe(100)
+      expect(job.params.homophily.wealth).toBe(1)
+      expect(job.params.homophily.gene).toBe(0)
+      expect(job.params.geneEnvWeight).toBe(0.2)
+      expect(job.generations).toBe(2)
+    })
+
+    it('should reuse replicate seeds across points', () => {
+      const jobs = makeJobs(spec, points)
+      expect(jobs.map(j => j.seed)).toEqual([10, 11, 12, 10, 11, 12])
+      expect(jobs.every(j => j.params.seed === j.seed)).toBe(true)
+    })
+
+    it('should reject unknown parameters', () => {
+      expect(() => makeJobs({ parameters: { 'homophily.welth': [1] } }, [{ 'homophily.welth': 1 }])).toThrow('welth')
+    })
+  })
+
+  describe('runJob', () => {
+    it('should be reproducible', () => {
+      const job = makeJobs(spec, points)[0]
+      expect(runJob(job)).toEqual(runJob(job))
+      expect(runJob(job).history.gini).toHaveLength(3)
+    })
+  })
+
+  describe('runJobs', () => {
+    it('should run inline with one worker and keep job order', async () => {
+      const jobs = makeJobs(spec, points)
+      const seen: number[] = []
+      const results = await runJobs(jobs, { workers: 1, onResult: r => seen.push(r.point) })
+      expect(results.map(r => [r.point, r.replicate])).toEqual(jobs.map(j => [j.point, j.replicate]))
+      expect(seen).toHaveLength(6)
+    })
+
+    it('should need a worker module to run in parallel', async () => {
+      await expect(runJobs(makeJobs(spec, points), { workers: 2 })).rejects.toThrow('worker')
+    })
+  })
+})
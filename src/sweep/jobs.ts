@@ -0,0 +1,49 @@
+// src/sweep/jobs.ts
+// One job per (point, replicate); the unit of work handed to a worker.
+import { Params } from '../model'
+import { History } from '../metrics/history'
+import { mergeParams } from '../params'
+import { runSimulation } from '../run'
+import { SweepPoint, SweepSpec, pointOverrides } from './design'
+
+export interface SweepJob {
+  point: number
+  replicate: number
+  seed: number
+  generations: number
+  params: Params
+}
+
+export interface SweepResult {
+  point: number
+  replicate: number
+  seed: number
+  history: History
+}
+
+/** Jobs for every point × replicate, point-major */
+export function makeJobs(spec: SweepSpec, points: SweepPoint[]): SweepJob[] {
+  const base = mergeParams(spec.base ?? {})
+  const replicates = spec.replicates ?? 1
+  const generations = spec.generations ?? 50
+  const firstSeed = spec.seed ?? 1
+  return points.flatMap((point, i) => {
+    const params = mergeParams(pointOverrides(point), base)
+    return Array.from({ length: replicates }, (_, r) => ({
+      point: i,
+      replicate: r,
+      seed: firstSeed + r,
+      generations,
+      params: { ...params, seed: firstSeed + r },
+    }))
+  })
+}
+
+export function runJob(job: SweepJob): SweepResult {
+  return {
+    point: job.point,
+    replicate: job.replicate,
+    seed: job.seed,
+    history: runSimulation(job.params, { generations: job.generations }),
+  }
+}
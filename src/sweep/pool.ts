@@ -0,0 +1,62 @@
+// src/sweep/pool.ts
+// Runs sweep jobs on a pool of worker threads, or inline for a single worker.
+import { Worker } from 'node:worker_threads'
+import { SweepJob, SweepResult, runJob } from './jobs'
+
+export interface PoolOptions {
+  /** Number of threads; 1 runs every job on the calling thread */
+  workers: number
+  /** Built worker module (dist/worker.mjs); required when workers > 1 */
+  workerUrl?: URL
+  /** Called as each job finishes, in completion order */
+  onResult?: (result: SweepResult) => void
+}
+
+/** Run all jobs and resolve with their results in job order */
+export function runJobs(jobs: SweepJob[], { workers, workerUrl, onResult }: PoolOptions): Promise<SweepResult[]> {
+  const results: SweepResult[] = new Array(jobs.length)
+  if (workers <= 1 || jobs.length <= 1) {
+    jobs.forEach((job, i) => {
+      results[i] = runJob(job)
+      onResult?.(results[i])
+    })
+    return Promise.resolve(results)
+  }
+  if (!workerUrl) return Promise.reject(new Error('A worker module is needed to run jobs in parallel'))
+
+  return new Promise((resolve, reject) => {
+    const pool: Worker[] = []
+    let next = 0
+    let finished = 0
+    const stop = () => pool.forEach(w => { void w.terminate() })
+
+    // each worker gets a new job as soon as it returns one
+    const feed = (worker: Worker) => {
+      if (next < jobs.length) {
+        worker.postMessage({ index: next, job: jobs[next] })
+        next += 1
+      }
+    }
+
+    for (let i = 0; i < Math.min(workers, jobs.length); i++) {
+      const worker = new Worker(workerUrl)
+      worker.on('message', ({ index, result }: { index: number, result: SweepResult }) => {
+        results[index] = result
+        onResult?.(result)
+        finished += 1
+        if (finished === jobs.length) {
+          stop()
+          resolve(results)
+        } else {
+          feed(worker)
+        }
+      })
+      worker.on('error', err => {
+        stop()
+        reject(err)
+      })
+      pool.push(worker)
+      feed(worker)
+    }
+  })
+}
@@ -0,0 +1,38 @@
+// src/sweep/tidy.test.ts
+import { describe, it, expect } from 'vitest'
+import { tidyHeader, tidyRows, summarizeResults } from './tidy'
+import type { SweepResult } from './jobs'
+
+const points = [{ geneEnvWeight: 0 }, { geneEnvWeight: 1 }]
+const result = (point: number, replicate: number, gini: number[]): SweepResult => ({
+  point, replicate, seed: replicate + 1, history: { gini, ige: [NaN, ...gini.slice(1)] },
+})
+
+describe('Sweep Output - tidy.ts', () => {
+  describe('tidyRows', () => {
+    it('should write one row per generation and metric', () => {
+      expect(tidyHeader(['geneEnvWeight'])).toBe('point,replicate,seed,geneEnvWeight,generation,metric,value\n')
+      expect(tidyRows(result(1, 0, [0.3, 0.4]), points[1], ['geneEnvWeight'])).toBe(
+        '1,0,1,1,0,gini,0.3\n' +
+        '1,0,1,1,0,ige,\n' +
+        '1,0,1,1,1,gini,0.4\n' +
+        '1,0,1,1,1,ige,0.4\n'
+      )
+    })
+  })
+
+  describe('summarizeResults', () => {
+    it('should average over replicates per point, generation and metric', () => {
+      const results = [result(0, 0, [0.2, 0.4]), result(0, 1, [0.4, 0.6]), result(1, 0, [0.5, 0.5])]
+      const lines = summarizeResults(results, points, ['geneEnvWeight']).trim().split('\n')
+
+      expect(lines[0]).toBe('point,geneEnvWeight,generation,metric,mean,sd,n')
+      // point 0, generation 0
+      expect(lines[1]).toMatch(/^0,0,0,gini,0\.3\d*,0\.14\d*,2$/)
+      // founders' IGE is NaN in every replicate
+      expect(lines[2]).toBe('0,0,0,ige,,,0')
+      // a single replicate has no sd
+      expect(lines).toContain('1,1,1,gini,0.5,,1')
+    })
+  })
+})
@@ -0,0 +1,55 @@
+// src/sweep/tidy.ts
+// Long-format sweep output and its replicate summary.
+import { historyLength } from '../metrics/history'
+import { mean, variance } from '../metrics/stats'
+import { csvRow } from '../csv'
+import { SweepPoint } from './design'
+import { SweepResult } from './jobs'
+
+/** Header of the tidy table: point, replicate, seed, swept parameters, generation, metric, value */
+export function tidyHeader(paramNames: string[]): string {
+  return csvRow(['point', 'replicate', 'seed', ...paramNames, 'generation', 'metric', 'value'])
+}
+
+/** One row per generation × metric of a finished run */
+export function tidyRows(result: SweepResult, point: SweepPoint, paramNames: string[]): string {
+  const prefix = [result.point, result.replicate, result.seed, ...paramNames.map(n => point[n])]
+  let csv = ''
+  for (let g = 0; g < historyLength(result.history); g++) {
+    Object.entries(result.history).forEach(([metric, values]) => {
+      csv += csvRow([...prefix, g, metric, values[g]])
+    })
+  }
+  return csv
+}
+
+/**
+ * Mean, sd and count of every metric over replicates, per point and generation.
+ * Non-finite values (e.g. IGE of the founders) are left out of the statistics.
+ */
+export function summarizeResults(results: SweepResult[], points: SweepPoint[], paramNames: string[]): string {
+  const cells = new Map<string, { point: number, generation: number, metric: string, values: number[] }>()
+  results.forEach(r => {
+    Object.entries(r.history).forEach(([metric, values]) => {
+      values.forEach((v, generation) => {
+        const key = `${r.point}|${generation}|${metric}`
+        if (!cells.has(key)) cells.set(key, { point: r.point, generation, metric, values: [] })
+        if (Number.isFinite(v)) cells.get(key)!.values.push(v)
+      })
+    })
+  })
+
+  let csv = csvRow(['point', ...paramNames, 'generation', 'metric', 'mean', 'sd', 'n'])
+  const sorted = [...cells.values()].sort((a, b) =>
+    a.point - b.point || a.generation - b.generation || a.metric.localeCompare(b.metric))
+  sorted.forEach(({ point, generation, metric, values }) => {
+    const n = values.length
+    csv += csvRow([
+      point, ...paramNames.map(name => points[point][name]), generation, metric,
+      n > 0 ? mean(values) : NaN,
+      n > 1 ? Math.sqrt(variance(values)) : NaN,
+      n,
+    ])
+  })
+  return csv
+}
@@ -0,0 +1,8 @@
+// src/sweep/worker.ts
+// Worker-thread entry point: runs the jobs posted by runJobs (see pool.ts).
+import { parentPort } from 'node:worker_threads'
+import { SweepJob, runJob } from './jobs'
+
+parentPort?.on('message', ({ index, job }: { index: number, job: SweepJob }) => {
+  parentPort?.postMessage({ index, result: runJob(job) })
+})
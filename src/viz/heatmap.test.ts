@@ -0,0 +1,31 @@
+// src/viz/heatmap.test.ts
+import { describe, it, expect } from 'vitest'
+import { averageMatrices } from './heatmap'
+
+describe('Visualization Functions - heatmap.ts', () => {
+  describe('averageMatrices', () => {
+    const identity = [[1, 0], [0, 1]]
+    const uniform = [[0.5, 0.5], [0.5, 0.5]]
+    const swap = [[0, 1], [1, 0]]
+
+    it('should average every matrix by default', () => {
+      expect(averageMatrices([identity, swap])).toEqual(uniform)
+    })
+
+    it('should average only the last n matrices', () => {
+      expect(averageMatrices([swap, identity, uniform], 2)).toEqual([[0.75, 0.25], [0.25, 0.75]])
+    })
+
+    it('should return the latest matrix when n is 1', () => {
+      expect(averageMatrices([identity, swap], 1)).toEqual(swap)
+    })
+
+    it('should handle n larger than the history', () => {
+      expect(averageMatrices([identity], 10)).toEqual(identity)
+    })
+
+    it('should return an empty matrix without history', () => {
+      expect(averageMatrices([])).toEqual([])
+    })
+  })
+})
@@ -0,0 +1,89 @@
+// src/viz/heatmap.ts
+import * as d3 from 'd3'
+
+/**
+ * Element-wise mean of the last `n` matrices (all of them if n exceeds the count).
+ * Used to smooth transition matrices over several generations.
+ */
+export function averageMatrices(matrices: number[][][], n = matrices.length): number[][] {
+  const recent = matrices.slice(-Math.max(1, n))
+  if (recent.length === 0) return []
+  return recent[0].map((row, i) =>
+    row.map((_, j) => d3.mean(recent, m => m[i][j]) ?? 0)
+  )
+}
+
+/**
+ * Draw a parent → child transition matrix as a heatmap: rows are the parents'
+ * quantile (bottom row = poorest), columns the child's, each cell labeled with
+ * its probability. Assumes svg has explicit width & height attributes.
+ */
+export function drawTransitionHeatmap(
+  svg: d3.Selection<SVGSVGElement, unknown, null, undefined>,
+  matrix: number[][]
+) {
+  const width  = +svg.attr('width')
+  const height = +svg.attr('height')
+  const margin = { top: 20, right: 20, bottom: 40, left: 50 }
+  const innerW = width - margin.left - margin.right
+  const innerH = height - margin.top - margin.bottom
+
+  svg.selectAll('*').remove()
+
+  const n = matrix.length
+  if (n === 0) return
+  const labels = d3.range(n).map(i => `Q${i + 1}`)
+
+  const x = d3.scaleBand<string>().domain(labels).range([0, innerW]).padding(0.05)
+  const y = d3.scaleBand<string>().domain([...labels].reverse()).range([0, innerH]).padding(0.05)
+  // a uniform matrix sits at 1/n; darker cells are more likely than chance
+  const color = d3.scaleSequential(d3.interpolateBlues)
+    .domain([0, Math.max(2 / n, d3.max(matrix.flat()) ?? 0)])
+
+  const g = svg.append('g')
+    .attr('transform', `translate(${margin.left},${margin.top})`)
+
+  const cells = matrix.flatMap((row, i) => row.map((p, j) => ({ i, j, p })))
+
+  g.selectAll('rect')
+    .data(cells)
+    .enter().append('rect')
+      .attr('x', d => x(labels[d.j]) as number)
+      .attr('y', d => y(labels[d.i]) as number)
+      .attr('width', x.bandwidth())
+      .attr('height', y.bandwidth())
+      .attr('fill', d => color(d.p))
+
+  g.selectAll('text.cell')
+    .data(cells)
+    .enter().append('text')
+      .attr('class', 'cell')
+      .attr('x', d => (x(labels[d.j]) as number) + x.bandwidth() / 2)
+      .attr('y', d => (y(labels[d.i]) as number) + y.bandwidth() / 2)
+      .attr('text-anchor', 'middle')
+      .attr('dominant-baseline', 'middle')
+      .attr('font-size', '10px')
+      .attr('fill', d => d.p > color.domain()[1] / 2 ? 'white' : 'black')
+      .text(d => d3.format('.0%')(d.p))
+
+  // axes
+  g.append('g')
+    .attr('transform', `translate(0,${innerH})`)
+    .call(d3.axisBottom(x))
+  g.append('text')
+    .attr('x', innerW / 2)
+    .attr('y', innerH + 32)
+    .attr('text-anchor', 'middle')
+    .attr('font-size', '10px')
+    .text('Child wealth quintile')
+
+  g.append('g')
+    .call(d3.axisLeft(y))
+  g.append('text')
+    .attr('transform', 'rotate(-90)')
+    .attr('x', -innerH / 2)
+    .attr('y', -36)
+    .attr('text-anchor', 'middle')
+    .attr('font-size', '10px')
+    .text('Parent wealth quintile')
+}
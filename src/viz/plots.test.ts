@@ -1,6 +1,6 @@
 // src/viz/plots.test.ts
 import { describe, it, expect } from 'vitest'
-import { computeLorenz, computeGini } from './plots'
+import { computeLorenz, computeGini, seriesDomain, nearestIndex, normalizedLabel } from './plots'
 import type { Agent } from '../model'
 
 describe('Visualization Functions - plots.ts', () => {
@@ -227,4 +227,52 @@ describe('Visualization Functions - plots.ts', () => {
       expect(gini).toBeCloseTo(0.267, 2)
     })
   })
+
+  describe('seriesDomain', () => {
+    it('should fit the data and include zero', () => {
+      expect(seriesDomain([{ name: 'a', values: [2, 5, 3] }])).toEqual([0, 5])
+      expect(seriesDomain([{ name: 'a', values: [-1, 0.5] }])).toEqual([-1, 0.5])
+    })
+
+    it('should ignore non-finite values', () => {
+      expect(seriesDomain([{ name: 'a', values: [NaN, 2, Infinity] }])).toEqual([0, 2])
+    })
+
+    it('should combine fixed domains with fitted ones', () => {
+      const domain = seriesDomain([
+        { name: 'gini', values: [0.3, 0.4], domain: [0, 1] },
+        { name: 'palma', values: [1.5, 2.5] },
+      ])
+      expect(domain).toEqual([0, 2.5])
+    })
+
+    it('should never return an empty domain', () => {
+      expect(seriesDomain([])).toEqual([0, 1])
+      expect(seriesDomain([{ name: 'a', values: [] }])).toEqual([0, 1])
+      expect(seriesDomain([{ name: 'a', values: [0, 0] }])).toEqual([0, 1])
+    })
+  })
+
+  describe('nearestIndex', () => {
+    it('should round to the nearest step', () => {
+      expect(nearestIndex(2.4, 10)).toBe(2)
+      expect(nearestIndex(2.6, 10)).toBe(3)
+    })
+
+    it('should clamp to the data', () => {
+      expect(nearestIndex(-3, 10)).toBe(0)
+      expect(nearestIndex(42, 10)).toBe(9)
+    })
+  })
+
+  describe('normalizedLabel', () => {
+    it('should name the bottom and top of the series range', () => {
+      expect(normalizedLabel({ name: 'Gini', values: [] }, [0, 1])).toBe('Gini (0–1)')
+    })
+
+    it('should use the series format', () => {
+      const s = { name: 'Top 1%', values: [], format: '.0%' }
+      expect(normalizedLabel(s, [0.1, 0.35])).toBe('Top 1% (10%–35%)')
+    })
+  })
 })
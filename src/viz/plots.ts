@@ -26,29 +26,8 @@ export function computeLorenz(pop: Agent[]): [number, number][] {
   return points
 }
 
-/**
- * Compute the Gini coefficient from an array of wealth values.
- * Gini = 1 - 2 * ∫₀¹ L(p) dp, approximated via the trapezoid rule on the Lorenz curve.
- */
-export function computeGini(wealth: number[]): number {
-  const N = wealth.length
-  if (N === 0) return 0
-
-  // reuse computeLorenz but only need the y-values
-  const lorenz = computeLorenz(
-    wealth.map(w => ({ wealth: w } as Agent))
-  )
-
-  // trapezoidal integration
-  let area = 0
-  for (let i = 1; i < lorenz.length; i++) {
-    const [x0, y0] = lorenz[i - 1]
-    const [x1, y1] = lorenz[i]
-    area += ((y0 + y1) / 2) * (x1 - x0)
-  }
-
-  return 1 - 2 * area
-}
+// the Gini coefficient lives with the other inequality indices
+export { computeGini } from '../metrics/inequality'
 
 /**
  * Draw a Lorenz curve into the given SVG element.
@@ -106,53 +85,207 @@ export function drawLorenzCurve(
     .call(d3.axisLeft(y).ticks(5))
 }
 
+/** One named line of a time-series chart, indexed by step (generation or year) */
+export interface TimeSeries {
+  name: string
+  values: number[]
+  /** Fixed y domain, e.g. [0, 1] for shares; otherwise fitted to the data */
+  domain?: [number, number]
+  /** d3-format specifier for tooltips */
+  format?: string
+}
+
 /**
- * Draw a time series of Gini coefficients into the given SVG.
- * Assumes svg has explicit width & height attributes.
+ * y domain shared by the given series: the union of their fixed domains and,
+ * for series without one, the range of their finite values (always including 0).
+ */
+export function seriesDomain(series: TimeSeries[]): [number, number] {
+  let lo = Infinity
+  let hi = -Infinity
+  series.forEach(s => {
+    const finite = s.values.filter(Number.isFinite)
+    const [sLo, sHi] = s.domain
+      ?? [Math.min(0, d3.min(finite) ?? 0), Math.max(0, d3.max(finite) ?? 1)]
+    lo = Math.min(lo, sLo)
+    hi = Math.max(hi, sHi)
+  })
+  if (!Number.isFinite(lo)) return [0, 1]
+  return hi > lo ? [lo, hi] : [lo, lo + 1]
+}
+
+/** Index of the step nearest to a (fractional) x position, clamped to the data */
+export function nearestIndex(x: number, length: number): number {
+  return Math.max(0, Math.min(length - 1, Math.round(x)))
+}
+
+/**
+ * Legend label for a series drawn normalized to its own y domain, naming the
+ * values at the bottom and top of the plot, e.g. "Gini (0–1)".
  */
-export function drawGiniTimeSeries(
+export function normalizedLabel(s: TimeSeries, [lo, hi]: [number, number]): string {
+  const f = d3.format(s.format ?? '.3~g')
+  return `${s.name} (${f(lo)}–${f(hi)})`
+}
+
+/**
+ * Draw several named series against the step index, with a legend and a hover
+ * tooltip listing every series' value. With sharedScale all series use one
+ * left axis; otherwise each gets its own y scale. Two such series are labeled
+ * on the left and right axes; with more, every series is normalized to its own
+ * range, the left axis runs from 0% to 100% of that range and the legend gives
+ * each series' bottom and top values. Assumes svg has explicit width & height
+ * attributes.
+ */
+export function drawTimeSeries(
   svg: d3.Selection<SVGSVGElement, unknown, null, undefined>,
-  history: number[]
+  series: TimeSeries[],
+  { sharedScale = true, xLabel = 'Generation' }: { sharedScale?: boolean, xLabel?: string } = {}
 ) {
   const width  = +svg.attr('width')
   const height = +svg.attr('height')
-  const margin = { top: 20, right: 20, bottom: 30, left: 40 }
+  const independent = !sharedScale && series.length > 1
+  const normalized  = independent && series.length > 2
+  const dualAxis    = independent && !normalized
+  const margin = { top: 20, right: dualAxis ? 45 : 20, bottom: 30, left: 45 }
   const innerW = width - margin.left - margin.right
   const innerH = height - margin.top - margin.bottom
 
   svg.selectAll('*').remove()
 
-  // scales: x = generation index, y = Gini [0,1]
+  const n = d3.max(series, s => s.values.length) ?? 0
   const x = d3.scaleLinear()
-    .domain([0, Math.max(1, history.length - 1)])
+    .domain([0, Math.max(1, n - 1)])
     .range([0, innerW])
 
-  const y = d3.scaleLinear()
-    .domain([0, 1])
-    .range([innerH, 0])
+  const sharedY = d3.scaleLinear().domain(seriesDomain(series)).range([innerH, 0]).nice()
+  const ys = series.map(s => independent
+    ? d3.scaleLinear().domain(seriesDomain([s])).range([innerH, 0]).nice()
+    : sharedY)
+  const color = d3.scaleOrdinal<string, string>(d3.schemeTableau10)
+    .domain(series.map(s => s.name))
 
   const g = svg.append('g')
     .attr('transform', `translate(${margin.left},${margin.top})`)
 
-  // line generator
-  const lineGen = d3.line<number>()
-    .x((d, i) => x(i))
-    .y(d => y(d))
-
-  g.append('path')
-    .datum(history)
-    .attr('d', lineGen as any)
-    .attr('fill', 'none')
-    .attr('stroke', 'steelblue')
-    .attr('stroke-width', 2)
+  // lines
+  series.forEach((s, k) => {
+    const lineGen = d3.line<number>()
+      .defined(d => Number.isFinite(d))
+      .x((d, i) => x(i))
+      .y(d => ys[k](d))
+
+    g.append('path')
+      .datum(s.values)
+      .attr('d', lineGen as any)
+      .attr('fill', 'none')
+      .attr('stroke', color(s.name))
+      .attr('stroke-width', 2)
+  })
 
-  // axes
+  // axes: integer steps only
   g.append('g')
     .attr('transform', `translate(0,${innerH})`)
-    .call(d3.axisBottom(x).ticks(5))
+    .call(d3.axisBottom(x).ticks(Math.min(5, Math.max(1, n - 1))).tickFormat(d3.format('d')))
 
-  g.append('g')
-    .call(d3.axisLeft(y).ticks(5))
+  if (normalized) {
+    g.append('g')
+      .call(d3.axisLeft(d3.scaleLinear().range([innerH, 0])).ticks(5, '.0%'))
+    g.append('text')
+      .attr('transform', 'rotate(-90)')
+      .attr('x', -innerH / 2)
+      .attr('y', -margin.left + 10)
+      .attr('text-anchor', 'middle')
+      .attr('font-size', '10px')
+      .text('Share of each series\' range')
+  } else {
+    const leftAxis = g.append('g')
+      .call(d3.axisLeft(ys[0] ?? sharedY).ticks(5))
+    if (dualAxis) leftAxis.selectAll('text').attr('fill', color(series[0].name))
+  }
+  if (dualAxis) {
+    g.append('g')
+      .attr('transform', `translate(${innerW},0)`)
+      .call(d3.axisRight(ys[1]).ticks(5))
+      .selectAll('text').attr('fill', color(series[1].name))
+  }
+
+  // legend, top left
+  if (series.length > 1) {
+    const labels = series.map((s, k) => normalized
+      ? normalizedLabel(s, ys[k].domain() as [number, number])
+      : s.name)
+    const legend = g.append('g').attr('transform', 'translate(8,4)')
+    legend.append('rect')
+      .attr('x', -4).attr('y', -2)
+      .attr('width', 24 + 6 * (d3.max(labels, l => l.length) ?? 0))
+      .attr('height', 12 * series.length + 4)
+      .attr('fill', 'white')
+      .attr('opacity', 0.8)
+    series.forEach((s, k) => {
+      const row = legend.append('g').attr('transform', `translate(0,${12 * k})`)
+      row.append('line')
+        .attr('x1', 0).attr('x2', 12)
+        .attr('y1', 6).attr('y2', 6)
+        .attr('stroke', color(s.name))
+        .attr('stroke-width', 2)
+      row.append('text')
+        .attr('x', 16).attr('y', 6)
+        .attr('dominant-baseline', 'middle')
+        .attr('font-size', '10px')
+        .text(labels[k])
+    })
+  }
+
+  // hover tooltip
+  const format = (s: TimeSeries, v: number) => Number.isFinite(v) ? d3.format(s.format ?? '.3~g')(v) : '–'
+  const focus = g.append('g').style('display', 'none')
+  const rule = focus.append('line')
+    .attr('y1', 0).attr('y2', innerH)
+    .attr('stroke', '#999')
+    .attr('stroke-dasharray', '3,3')
+  const dots = focus.selectAll('circle')
+    .data(series)
+    .enter().append('circle')
+      .attr('r', 3)
+      .attr('fill', s => color(s.name))
+  const tip = focus.append('g')
+  const tipBox = tip.append('rect')
+    .attr('fill', 'white')
+    .attr('stroke', '#ccc')
+    .attr('opacity', 0.9)
+  const tipText = tip.append('text').attr('font-size', '10px')
+
+  g.append('rect')
+    .attr('width', innerW)
+    .attr('height', innerH)
+    .attr('fill', 'none')
+    .attr('pointer-events', 'all')
+    .on('mouseenter', () => { if (n > 0) focus.style('display', null) })
+    .on('mouseleave', () => focus.style('display', 'none'))
+    .on('mousemove', (event: MouseEvent) => {
+      if (n === 0) return
+      const i = nearestIndex(x.invert(d3.pointer(event)[0]), n)
+      const xi = x(i)
+      rule.attr('x1', xi).attr('x2', xi)
+      dots
+        .attr('cx', xi)
+        .attr('cy', (s, k) => ys[k](s.values[i]))
+        .style('display', s => Number.isFinite(s.values[i]) ? null : 'none')
+
+      const lines = [`${xLabel} ${i}`, ...series.map(s => `${s.name}: ${format(s, s.values[i])}`)]
+      tipText.selectAll('tspan')
+        .data(lines)
+        .join('tspan')
+          .attr('x', 4)
+          .attr('dy', (_, j) => j === 0 ? '1.1em' : '1.2em')
+          .text(d => d)
+      const boxW = 8 + 5.5 * (d3.max(lines, l => l.length) ?? 0)
+      const boxH = 6 + 12 * lines.length
+      tipBox.attr('width', boxW).attr('height', boxH)
+      // keep the tooltip on the side of the rule with more room
+      const tipX = xi + 8 + boxW > innerW ? xi - 8 - boxW : xi + 8
+      tip.attr('transform', `translate(${tipX},${Math.max(0, innerH - boxH) / 2})`)
+    })
 }
 
 /** 
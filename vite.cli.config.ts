@@ -0,0 +1,25 @@
+import { defineConfig } from 'vite'
+
+// Node build of the headless CLI: `npm run build:cli` → dist/cli.mjs, plus the
+// sweep worker dist/worker.mjs that cli.mjs loads from its own directory
+export default defineConfig({
+  build: {
+    ssr: true,
+    outDir: 'dist',
+    emptyOutDir: true,
+    target: 'node22',
+    rollupOptions: {
+      input: {
+        cli: 'src/cli/index.ts',
+        worker: 'src/sweep/worker.ts',
+      },
+      output: {
+        entryFileNames: '[name].mjs',
+        chunkFileNames: '[name]-[hash].mjs',
+        banner: chunk => chunk.name === 'cli' ? '#!/usr/bin/env node' : '',
+      },
+    },
+  },
+  // bundle jstat & co. so the CLI runs without resolving CommonJS named exports
+  ssr: { noExternal: true },
+})